@@ -0,0 +1,392 @@
+//! Compact binary encoding of complex and hypercomplex numbers.
+//!
+//! A number is encoded as its `DIM` components in Cayley-Dickson order, the
+//! order of `Complex::as_slice`, each in the byte order chosen by `Endian`
+//! and without any framing, so an octonion of `f64` takes 64 bytes. Files
+//! holding many numbers can start with a `Header` recording the byte order,
+//! the scalar type and the dimension, which readers check before decoding.
+//!
+//! # Example
+//!
+//! ```
+//! use complex::*;
+//! use complex::bytes::{Endian, Header};
+//!
+//! let values = [complex![1., 2., 3., 4.], complex![-1., 0.5, 0., 8.]];
+//! let mut file = Vec::new();
+//!
+//! Header::new::<Quaternionf64>(Endian::Big).write_to(&mut file).unwrap();
+//! for q in values.iter() {
+//!     q.write_to(&mut file, Endian::Big).unwrap();
+//! }
+//! assert_eq!(file.len(), Header::SIZE + 2 * 32);
+//!
+//! let mut reader = &file[..];
+//! let header = Header::read_from(&mut reader).unwrap();
+//! header.check::<Quaternionf64>().unwrap();
+//! assert!(header.check::<Octonionf64>().is_err());
+//!
+//! let first = Quaternionf64::read_from(&mut reader, header.endian).unwrap();
+//! let second = Quaternionf64::read_from(&mut reader, header.endian).unwrap();
+//! assert_eq!([first, second], values);
+//! ```
+use crate::*;
+use std::convert::TryInto;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// Scalars with a fixed size binary representation.
+pub trait ScalarBytes: Scalar {
+    /// Kind of number recorded in headers, `'f'` for floating point.
+    const KIND: u8;
+    /// Size of the encoding in bytes.
+    const SIZE: usize;
+
+    /// Writes the little-endian bytes into `out` of length `SIZE`.
+    fn write_le(self, out: &mut [u8]);
+    /// Writes the big-endian bytes into `out` of length `SIZE`.
+    fn write_be(self, out: &mut [u8]);
+    /// Reads a number from `SIZE` little-endian bytes.
+    fn read_le(bytes: &[u8]) -> Self;
+    /// Reads a number from `SIZE` big-endian bytes.
+    fn read_be(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_scalar_bytes_for_float {
+    ( $($u:ident),* ) => {
+        $(
+            impl ScalarBytes for $u {
+                const KIND: u8 = b'f';
+                const SIZE: usize = std::mem::size_of::<$u>();
+
+                fn write_le(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn write_be(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_be_bytes());
+                }
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    $u::from_le_bytes(bytes.try_into().unwrap())
+                }
+
+                fn read_be(bytes: &[u8]) -> Self {
+                    $u::from_be_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_bytes_for_float!(f32, f64);
+
+/// Byte order of the encoded components.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Endian {
+    /// Least significant byte first.
+    #[default]
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+/// Header of a self-describing file, recording how the numbers following it
+/// are encoded. It takes `Header::SIZE` bytes: the magic `CDX1`, `<` or `>`
+/// for the byte order, the kind and size of the scalar, a zero byte and the
+/// dimension as a `u32` in the recorded byte order.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Header {
+    /// Byte order of the components.
+    pub endian: Endian,
+    /// Kind of the scalar, `b'f'` for floating point.
+    pub kind: u8,
+    /// Size of the scalar in bytes.
+    pub size: u8,
+    /// Number of components of every number.
+    pub dim: u32,
+}
+
+impl Header {
+    /// Size of an encoded header in bytes.
+    pub const SIZE: usize = 12;
+    const MAGIC: &'static [u8; 4] = b"CDX1";
+
+    /// Creates the header describing numbers of type `H`, which may be a
+    /// scalar or any `Complex<T>`.
+    pub fn new<H>(endian: Endian) -> Self
+    where
+        H: Hypercomplex,
+        H::Scalar: ScalarBytes,
+    {
+        Self {
+            endian,
+            kind: H::Scalar::KIND,
+            size: H::Scalar::SIZE as u8,
+            dim: H::DIM as u32,
+        }
+    }
+
+    /// Checks that numbers of type `H` are described by the header.
+    pub fn check<H>(&self) -> Result<(), DecodeError>
+    where
+        H: Hypercomplex,
+        H::Scalar: ScalarBytes,
+    {
+        let expected = Self::new::<H>(self.endian);
+        if *self == expected {
+            Ok(())
+        } else {
+            Err(DecodeError::Header {
+                expected,
+                actual: *self,
+            })
+        }
+    }
+
+    /// Returns the encoded header.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[..4].copy_from_slice(Self::MAGIC);
+        let dim = match self.endian {
+            Endian::Little => {
+                bytes[4] = b'<';
+                self.dim.to_le_bytes()
+            }
+            Endian::Big => {
+                bytes[4] = b'>';
+                self.dim.to_be_bytes()
+            }
+        };
+        bytes[5] = self.kind;
+        bytes[6] = self.size;
+        bytes[8..].copy_from_slice(&dim);
+        bytes
+    }
+
+    /// Decodes a header from exactly `Header::SIZE` bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != Self::SIZE {
+            return Err(DecodeError::Length {
+                expected: Self::SIZE,
+                actual: bytes.len(),
+            });
+        }
+        let dim = bytes[8..].try_into().unwrap();
+        let (endian, dim) = match (&bytes[..4] == Self::MAGIC, bytes[4], bytes[7]) {
+            (true, b'<', 0) => (Endian::Little, u32::from_le_bytes(dim)),
+            (true, b'>', 0) => (Endian::Big, u32::from_be_bytes(dim)),
+            _ => return Err(DecodeError::Magic),
+        };
+
+        Ok(Self {
+            endian,
+            kind: bytes[5],
+            size: bytes[6],
+            dim,
+        })
+    }
+
+    /// Writes the encoded header.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Reads a header, failing with `io::ErrorKind::InvalidData` if the bytes
+    /// are not one.
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; Self::SIZE];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes)?)
+    }
+}
+
+/// Error returned when decoding bytes fails.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The number of bytes does not match the encoded type.
+    Length { expected: usize, actual: usize },
+    /// The bytes do not start with a header.
+    Magic,
+    /// The header describes a different type than the one decoded.
+    Header { expected: Header, actual: Header },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Length { expected, actual } => {
+                write!(f, "Expected {} bytes but found {}.", expected, actual)
+            }
+            DecodeError::Magic => write!(f, "Invalid header."),
+            DecodeError::Header { expected, actual } => write!(
+                f,
+                "Expected {} components of {}{} but the header records {} of {}{}.",
+                expected.dim,
+                expected.kind as char,
+                expected.size,
+                actual.dim,
+                actual.kind as char,
+                actual.size
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<DecodeError> for io::Error {
+    fn from(err: DecodeError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: Hypercomplex,
+    T::Scalar: ScalarBytes,
+{
+    /// Number of bytes of the encoding.
+    const BYTES: usize = Self::DIM * <T::Scalar as ScalarBytes>::SIZE;
+
+    fn encode(&self, endian: Endian, out: &mut [u8]) {
+        let size = <T::Scalar as ScalarBytes>::SIZE;
+        for (x, chunk) in self.components().zip(out.chunks_exact_mut(size)) {
+            match endian {
+                Endian::Little => x.write_le(chunk),
+                Endian::Big => x.write_be(chunk),
+            }
+        }
+    }
+
+    fn decode(bytes: &[u8], endian: Endian) -> Result<Self, DecodeError>
+    where
+        Self: Identity,
+    {
+        if bytes.len() != Self::BYTES {
+            return Err(DecodeError::Length {
+                expected: Self::BYTES,
+                actual: bytes.len(),
+            });
+        }
+        let size = <T::Scalar as ScalarBytes>::SIZE;
+        let mut z = Self::zero();
+        for (x, chunk) in z.as_mut_slice().iter_mut().zip(bytes.chunks_exact(size)) {
+            *x = match endian {
+                Endian::Little => ScalarBytes::read_le(chunk),
+                Endian::Big => ScalarBytes::read_be(chunk),
+            };
+        }
+        Ok(z)
+    }
+
+    /// Returns the components in Cayley-Dickson order as little-endian bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![1f32, -2f32];
+    ///
+    /// assert_eq!(z.to_le_bytes(), [0, 0, 128, 63, 0, 0, 0, 192]);
+    /// ```
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::BYTES];
+        self.encode(Endian::Little, &mut bytes);
+        bytes
+    }
+
+    /// Returns the components in Cayley-Dickson order as big-endian bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![1f32, -2f32];
+    ///
+    /// assert_eq!(z.to_be_bytes(), [63, 128, 0, 0, 192, 0, 0, 0]);
+    /// ```
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::BYTES];
+        self.encode(Endian::Big, &mut bytes);
+        bytes
+    }
+
+    /// Decodes a number from the little-endian bytes of its components,
+    /// failing unless there are exactly `DIM` times the scalar size of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let o = complex![1., 2., 3., 4., 5., 6., 7., 8.];
+    ///
+    /// assert_eq!(Octonionf64::from_le_bytes(&o.to_le_bytes()), Ok(o));
+    /// assert!(Octonionf64::from_le_bytes(&o.to_le_bytes()[1..]).is_err());
+    /// ```
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<Self, DecodeError>
+    where
+        Self: Identity,
+    {
+        Self::decode(bytes, Endian::Little)
+    }
+
+    /// Decodes a number from the big-endian bytes of its components, failing
+    /// unless there are exactly `DIM` times the scalar size of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![1., -2., 3., -4.];
+    ///
+    /// assert_eq!(Quaternionf64::from_be_bytes(&q.to_be_bytes()), Ok(q));
+    /// ```
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<Self, DecodeError>
+    where
+        Self: Identity,
+    {
+        Self::decode(bytes, Endian::Big)
+    }
+
+    /// Writes the bytes of the components in the given byte order.
+    pub fn write_to<W: Write>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        // large enough for the components of a trigintaduonion of `f64`
+        let mut buffer = [0; 256];
+        match buffer.get_mut(..Self::BYTES) {
+            Some(bytes) => {
+                self.encode(endian, bytes);
+                writer.write_all(bytes)
+            }
+            None => {
+                let mut bytes = vec![0; Self::BYTES];
+                self.encode(endian, &mut bytes);
+                writer.write_all(&bytes)
+            }
+        }
+    }
+
+    /// Reads a number from the bytes of its components in the given byte
+    /// order.
+    pub fn read_from<R: Read>(reader: &mut R, endian: Endian) -> io::Result<Self>
+    where
+        Self: Identity,
+    {
+        let mut buffer = [0; 256];
+        let mut heap;
+        let bytes = match buffer.get_mut(..Self::BYTES) {
+            Some(bytes) => bytes,
+            None => {
+                heap = vec![0; Self::BYTES];
+                &mut heap[..]
+            }
+        };
+        reader.read_exact(bytes)?;
+        Ok(Self::decode(bytes, endian)?)
+    }
+}
@@ -0,0 +1,308 @@
+//! Dual quaternions `q = r + εd`, with `ε² = 0`, representing rigid motions of
+//! three dimensional space.
+//!
+//! A unit dual quaternion has a unit real part `r`, the rotation, and a dual
+//! part `d = t r / 2` orthogonal to it, where `t` is the translation applied
+//! after the rotation as a pure imaginary quaternion. Motions compose by
+//! multiplication like rotations in the `rotation` module, `q2 * q1` applying
+//! `q1` first, and `q` and `-q` give the same motion.
+//!
+//! # Example
+//!
+//! ```
+//! use complex::*;
+//! use std::f64::consts::FRAC_PI_2;
+//!
+//! let turn = Quaternionf64::from_axis_angle([0., 0., 1.], FRAC_PI_2);
+//! let q = DualQuaternionf64::from_rotation_translation(turn, [1., 2., 3.]);
+//! let [x, y, z] = q.transform_point([1., 0., 0.]);
+//!
+//! assert!((x - 1.).abs() < 1e-12 && (y - 3.).abs() < 1e-12 && (z - 3.).abs() < 1e-12);
+//! ```
+use crate::rotation::cross;
+use crate::*;
+
+/// A dual number whose real and dual parts are quaternions, see the module
+/// documentation.
+///
+/// With the `serde` feature it serializes as the structure `{real, dual}`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[repr(C)]
+pub struct DualQuaternion<T> {
+    pub real: Complex<Complex<T>>,
+    pub dual: Complex<Complex<T>>,
+}
+
+/// A dual quaternion made of `Quaternionf64`.
+pub type DualQuaternionf64 = DualQuaternion<f64>;
+/// A dual quaternion made of `Quaternionf32`.
+pub type DualQuaternionf32 = DualQuaternion<f32>;
+
+impl<T> DualQuaternion<T> {
+    /// Creates a dual quaternion from its real and dual parts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = DualQuaternionf64::new(complex![1., 0., 0., 0.], complex![0., 0.5, 0., 0.]);
+    ///
+    /// assert_eq!(q.dual.re.im, 0.5);
+    /// ```
+    pub fn new(real: Complex<Complex<T>>, dual: Complex<Complex<T>>) -> Self {
+        Self { real, dual }
+    }
+}
+
+impl<S: Scalar> DualQuaternion<S> {
+    /// Creates the unit dual quaternion of the motion rotating by `rotation`
+    /// and then translating by `translation`. The rotation does not need to
+    /// be normalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = DualQuaternionf64::from_rotation_translation(Quaternionf64::one(), [2., 0., -4.]);
+    ///
+    /// assert_eq!(q, DualQuaternion::new(complex![1., 0., 0., 0.], complex![0., 1., 0., -2.]));
+    /// ```
+    pub fn from_rotation_translation(rotation: Complex<Complex<S>>, translation: [S; 3]) -> Self {
+        let real = rotation.normalize();
+        let dual = Complex::from_vector(translation) * real * S::from_f64(0.5);
+
+        Self { real, dual }
+    }
+
+    /// Returns the unit rotation and the translation of the motion, the
+    /// inverse of `from_rotation_translation`. The dual quaternion is
+    /// normalized first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let r = Quaternionf64::from_axis_angle([1., 1., 0.], 0.5);
+    /// let q = DualQuaternionf64::from_rotation_translation(r, [0.5, -1., 2.]) * 3.;
+    /// let (rotation, [x, y, z]) = q.to_rotation_translation();
+    ///
+    /// assert!((rotation - r).abs() < 1e-12);
+    /// assert!((x - 0.5).abs() < 1e-12 && (y + 1.).abs() < 1e-12 && (z - 2.).abs() < 1e-12);
+    /// ```
+    pub fn to_rotation_translation(&self) -> (Complex<Complex<S>>, [S; 3]) {
+        let q = self.normalize();
+        let translation = (q.dual * q.real.conj() * S::from_f64(2.)).vector();
+
+        (q.real, translation)
+    }
+
+    /// Returns the dual conjugate `r - εd`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = DualQuaternionf64::new(complex![1., 2., 3., 4.], complex![5., 6., 7., 8.]);
+    ///
+    /// assert_eq!(q.dual_conj(), DualQuaternion::new(q.real, -q.dual));
+    /// ```
+    pub fn dual_conj(&self) -> Self {
+        Self {
+            real: self.real,
+            dual: -self.dual,
+        }
+    }
+
+    /// Returns the combined conjugate `r* - εd*`, both the quaternion and the
+    /// dual conjugate. Points `p` transform as `q (1 + εp) q̅` with it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = DualQuaternionf64::new(complex![1., 2., 3., 4.], complex![5., 6., 7., 8.]);
+    ///
+    /// assert_eq!(q.combined_conj(), q.conj().dual_conj());
+    /// assert_eq!(q.combined_conj().dual, complex![-5., 6., 7., 8.]);
+    /// ```
+    pub fn combined_conj(&self) -> Self {
+        Self {
+            real: self.real.conj(),
+            dual: -self.dual.conj(),
+        }
+    }
+
+    /// Returns the unit dual quaternion of the same motion, dividing by the
+    /// dual number norm `|r| + ε (r·d) / |r|`. The result has a unit real
+    /// part and a dual part orthogonal to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = DualQuaternionf64::new(complex![2., 0., 0., 0.], complex![1., 3., 0., 0.]);
+    /// let p = q.normalize();
+    ///
+    /// assert_eq!(p, DualQuaternion::new(complex![1., 0., 0., 0.], complex![0., 1.5, 0., 0.]));
+    /// assert_eq!(p.real.dot(&p.dual), 0.);
+    /// ```
+    pub fn normalize(&self) -> Self {
+        let norm = self.real.abs();
+        let real = self.real / norm;
+
+        Self {
+            real,
+            dual: self.dual / norm - real * (real.dot(&self.dual) / norm),
+        }
+    }
+
+    /// Applies the motion to a point, rotating and then translating it. The
+    /// dual quaternion does not need to be normalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let turn = Quaternionf64::from_axis_angle([0., 1., 0.], PI);
+    /// let a = DualQuaternionf64::from_rotation_translation(turn, [0., 0., 1.]);
+    /// let b = DualQuaternionf64::from_rotation_translation(Quaternionf64::one(), [1., 0., 0.]);
+    /// let [x, y, z] = (b * a).transform_point([1., 1., 1.]);
+    ///
+    /// assert!(x.abs() < 1e-12 && (y - 1.).abs() < 1e-12 && z.abs() < 1e-12);
+    /// ```
+    pub fn transform_point(&self, p: [S; 3]) -> [S; 3] {
+        let q = self.normalize();
+        let point = Self {
+            real: Complex::one(),
+            dual: Complex::from_vector(p),
+        };
+
+        (q * point * q.combined_conj()).dual.vector()
+    }
+
+    /// Raises the motion to a real power along its screw axis: rotating by
+    /// `t` times its angle about the axis and translating by `t` times its
+    /// distance along it. The dual quaternion is normalized first, and pure
+    /// translations are scaled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let r = Quaternionf64::from_axis_angle([0., 0., 1.], 1.2);
+    /// let q = DualQuaternionf64::from_rotation_translation(r, [1., -2., 0.5]);
+    /// let half = q.powf(0.5);
+    ///
+    /// assert!((half * half).real.dot(&q.real) > 1. - 1e-12);
+    /// assert!(((half * half).dual - q.dual).abs() < 1e-12);
+    /// ```
+    pub fn powf(&self, t: S) -> Self {
+        let half = S::from_f64(0.5);
+        let (rotation, translation) = self.to_rotation_translation();
+        let cos = rotation.re.re;
+        let [x, y, z] = rotation.vector();
+        let sin = x.hypot(y).hypot(z);
+        let angle = sin.atan2(cos) * t;
+        let (sin_t, cos_t) = (angle.sin(), angle.cos());
+
+        // the direction of the screw axis is arbitrary for pure translations,
+        // where the ratio of the sines tends to `t`
+        let (axis, ratio) = if sin == S::zero() {
+            ([S::one(), S::zero(), S::zero()], t)
+        } else {
+            ([x / sin, y / sin, z / sin], sin_t / sin)
+        };
+        let distance =
+            translation[0] * axis[0] + translation[1] * axis[1] + translation[2] * axis[2];
+        let along = distance * t * half;
+
+        // the moment of the screw axis times the sine, computed from the
+        // translation to stay finite for small rotations
+        let turn = cross(translation, axis);
+        let moment = [0, 1, 2].map(|n| {
+            let perpendicular = translation[n] - axis[n] * distance;
+            (turn[n] * sin_t + perpendicular * cos * ratio) * half
+        });
+
+        Self {
+            real: complex![cos_t, axis[0] * sin_t, axis[1] * sin_t, axis[2] * sin_t],
+            dual: complex![
+                -along * sin_t,
+                axis[0] * along * cos_t + moment[0],
+                axis[1] * along * cos_t + moment[1],
+                axis[2] * along * cos_t + moment[2]
+            ],
+        }
+    }
+
+    /// Screw linear interpolation between motions, rotating and translating
+    /// at constant rates along a single screw from the dual quaternion at
+    /// `t = 0` to `other` at `t = 1`. Like `slerp` it takes the shortest arc
+    /// between the rotations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let start = DualQuaternionf64::from_rotation_translation(Quaternionf64::one(), [0., 0., 0.]);
+    /// let turn = Quaternionf64::from_axis_angle([0., 0., 1.], FRAC_PI_2);
+    /// let end = DualQuaternionf64::from_rotation_translation(turn, [0., 0., 2.]);
+    /// let (rotation, [x, y, z]) = start.sclerp(&-end, 0.5).to_rotation_translation();
+    ///
+    /// assert!((rotation - Quaternionf64::from_axis_angle([0., 0., 1.], FRAC_PI_2 / 2.)).abs() < 1e-12);
+    /// assert!(x.abs() < 1e-12 && y.abs() < 1e-12 && (z - 1.).abs() < 1e-12);
+    /// ```
+    pub fn sclerp(&self, other: &Self, t: S) -> Self {
+        let q = self.normalize();
+        let other = if q.real.dot(&other.real) < S::zero() {
+            -other.normalize()
+        } else {
+            other.normalize()
+        };
+
+        q * (q.conj() * other).powf(t)
+    }
+}
+
+impl<S: Scalar> Identity for DualQuaternion<S> {
+    fn zero() -> Self {
+        Self {
+            real: Complex::zero(),
+            dual: Complex::zero(),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            real: Complex::one(),
+            dual: Complex::zero(),
+        }
+    }
+}
+
+/// The quaternion conjugate `r* + εd*`, the inverse motion of a unit dual
+/// quaternion.
+impl<S: Scalar> Conjugate for DualQuaternion<S> {
+    fn conj(&self) -> Self {
+        Self {
+            real: self.real.conj(),
+            dual: self.dual.conj(),
+        }
+    }
+}
@@ -1,188 +1,1159 @@
 //! Includes implementions of string conversion, printing and parsing
 //! for complex and hypercomplex types.
+//!
+//! `FromStr` reads flat sums of terms like `1 - 2i + 3j`, with the units of
+//! any `Units` style, nested pairs `(x, y)` for octonions and above, and the
+//! polar forms printed by `Complex::display_polar`. An indexed unit must be
+//! separated from its coefficient by `*` or a space, as in `2*e1` or `2 e1`,
+//! since `2e1` is the number twenty in scientific notation.
+//!
+//! # Example
+//!
+//! ```
+//! use complex::*;
+//!
+//! assert_eq!("2e1".parse::<Octonionf64>(), Ok(Octonionf64::one() * 20.));
+//! assert_eq!("2*e1".parse::<Octonionf64>(), Ok(Octonionf64::e(1) * 2.));
+//! assert_eq!("1 + 2 e3".parse::<Quaternionf64>(), Ok(complex![1., 0., 0., 2.]));
+//! ```
 use crate::*;
-use regex::Regex;
-use std::any::type_name;
-use std::{fmt, str::FromStr};
+use std::fmt::{self, Write};
+use std::str::FromStr;
 
-fn type_of<T>(_: &T) -> &'static str {
-    type_name::<T>()
+/// Names of the imaginary units used when printing complex and hypercomplex
+/// numbers. Parsing accepts all of them regardless.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Units {
+    /// `i` for complex numbers, `i`, `j`, `k` for quaternions and `e1`, `e2`,
+    /// ... for octonions and above.
+    #[default]
+    Standard,
+    /// Like `Standard`, but with `i`, `j`, `k`, `l`, `il`, `jl`, `kl` for
+    /// octonions.
+    Named,
+    /// `e1`, `e2`, ... at every level.
+    Indexed,
 }
 
-impl<T> fmt::Display for Complex<T>
+const QUATERNION_UNITS: [&str; 4] = ["", "i", "j", "k"];
+const OCTONION_UNITS: [&str; 8] = ["", "i", "j", "k", "l", "il", "jl", "kl"];
+
+/// The name of an imaginary unit, written differently by each output format.
+#[derive(Clone, Copy)]
+enum UnitName {
+    /// A unit named by letters, like `i` or `il`.
+    Letters(&'static str),
+    /// A unit named by its index, like `e5`.
+    Indexed(usize),
+}
+
+impl Units {
+    /// Returns the name of the `n`-th unit at the given level, nothing for
+    /// the real unit.
+    fn name(self, level: u32, n: usize) -> Option<UnitName> {
+        match (self, level) {
+            _ if n == 0 => None,
+            (Units::Standard, 1..=2) | (Units::Named, 1..=2) => {
+                Some(UnitName::Letters(QUATERNION_UNITS[n]))
+            }
+            (Units::Named, 3) => Some(UnitName::Letters(OCTONION_UNITS[n])),
+            _ => Some(UnitName::Indexed(n)),
+        }
+    }
+}
+
+/// Prints a complex or hypercomplex number as a flat sum of its components
+/// times their units, like `1 + 2i - 3j + 4k`, created by `Complex::notation`.
+#[derive(Debug, Clone, Copy)]
+pub struct Notation<'a, T> {
+    z: &'a Complex<T>,
+    units: Units,
+    all_terms: bool,
+}
+
+impl<'a, T> Notation<'a, T> {
+    /// Selects the names of the imaginary units.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use complex::fmt::Units;
+    ///
+    /// let o = complex![1., 2., 3., 4., 5., 6., 7., 8.];
+    ///
+    /// assert_eq!(
+    ///     o.notation().units(Units::Named).to_string(),
+    ///     "1 + 2i + 3j + 4k + 5l + 6il + 7jl + 8kl"
+    /// );
+    /// assert_eq!(
+    ///     complex![1., -2.].notation().units(Units::Indexed).to_string(),
+    ///     "1 - 2*e1"
+    /// );
+    /// ```
+    pub fn units(self, units: Units) -> Self {
+        Self { units, ..self }
+    }
+
+    /// Selects whether terms with a positive zero component are printed,
+    /// which they are not by default. Printing all terms lines up the
+    /// components of numbers printed with a width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![0., 2., 0., -0.];
+    ///
+    /// assert_eq!(q.to_string(), "2i - 0k");
+    /// assert_eq!(q.notation().all_terms(true).to_string(), "0 + 2i + 0j - 0k");
+    /// ```
+    pub fn all_terms(self, all_terms: bool) -> Self {
+        Self { all_terms, ..self }
+    }
+}
+
+impl<T> Complex<T> {
+    /// Creates an adapter for printing in flat notation with options, the
+    /// `Display` implementation uses the default ones.
+    pub fn notation(&self) -> Notation<'_, T> {
+        Notation {
+            z: self,
+            units: Units::default(),
+            all_terms: false,
+        }
+    }
+
+    /// Creates an adapter for printing as LaTeX math.
+    pub fn latex(&self) -> Latex<'_, T> {
+        Latex {
+            z: self,
+            units: Units::default(),
+            all_terms: false,
+            bold: false,
+        }
+    }
+
+    /// Creates an adapter for printing as MathML.
+    pub fn mathml(&self) -> MathMl<'_, T> {
+        MathMl {
+            z: self,
+            units: Units::default(),
+            all_terms: false,
+            bold: false,
+        }
+    }
+
+    /// Creates an adapter for printing in polar form.
+    pub fn display_polar(&self) -> PolarForm<'_, T> {
+        PolarForm {
+            z: self,
+            style: PolarStyle::default(),
+            units: Units::default(),
+        }
+    }
+}
+
+/// Prints a complex or hypercomplex number as LaTeX math, like
+/// `1 - 2i + 3e_{2}`, created by `Complex::latex`. The precision and the `+`
+/// flag of the formatter apply like for `Display`.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use complex::fmt::Units;
+///
+/// let q = complex![1., -2., 0., 0.5];
+///
+/// assert_eq!(q.latex().to_string(), "1 - 2i + 0.5k");
+/// assert_eq!(
+///     format!("{:.1}", q.latex().units(Units::Indexed).bold(true)),
+///     r"1.0 - 2.0\mathbf{e}_{1} + 0.5\mathbf{e}_{3}"
+/// );
+/// assert_eq!(
+///     complex![f64::NAN, -f64::INFINITY].latex().to_string(),
+///     r"\mathrm{NaN} - \infty\,i"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Latex<'a, T> {
+    z: &'a Complex<T>,
+    units: Units,
+    all_terms: bool,
+    bold: bool,
+}
+
+/// Prints a complex or hypercomplex number as a MathML `math` element,
+/// created by `Complex::mathml`. The precision and the `+` flag of the
+/// formatter apply like for `Display`.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+///
+/// let z = complex![1.5, -2.];
+///
+/// assert_eq!(
+///     z.mathml().to_string(),
+///     "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>\
+///      <mn>1.5</mn><mo>-</mo><mn>2</mn><mo>&#x2062;</mo><mi>i</mi>\
+///      </mrow></math>"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MathMl<'a, T> {
+    z: &'a Complex<T>,
+    units: Units,
+    all_terms: bool,
+    bold: bool,
+}
+
+macro_rules! markup_options {
+    ($adapter:ident) => {
+        impl<'a, T> $adapter<'a, T> {
+            /// Selects the names of the imaginary units.
+            pub fn units(self, units: Units) -> Self {
+                Self { units, ..self }
+            }
+
+            /// Selects whether terms with a positive zero component are
+            /// printed, which they are not by default.
+            pub fn all_terms(self, all_terms: bool) -> Self {
+                Self { all_terms, ..self }
+            }
+
+            /// Selects whether the imaginary units are set in bold, as in
+            /// `\mathbf{i}`.
+            pub fn bold(self, bold: bool) -> Self {
+                Self { bold, ..self }
+            }
+        }
+    };
+}
+
+markup_options!(Latex);
+markup_options!(MathMl);
+
+impl<'a, T> fmt::Display for Latex<'a, T>
 where
-    T: fmt::Display,
+    T: Hypercomplex,
+    T::Scalar: fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if (type_name::<T>() == "f64") || (type_name::<T>() == "f32") {
-            let real = self.re.to_string();
-            let imag = self.im.to_string();
+        let level = Complex::<T>::LEVEL;
 
-            if &imag[0..1] != "-" {
-                return write!(f, "{} + {}i", &real, &imag);
-            } else {
-                return write!(f, "{} - {}i", &real, &imag[1..]);
+        for (position, term) in terms(self.z, self.all_terms, f, display_component)
+            .iter()
+            .enumerate()
+        {
+            f.write_str(sign(position, term.negative, f))?;
+            match term.magnitude.as_str() {
+                _ if term.is_numeric() => f.write_str(&term.magnitude)?,
+                "inf" => f.write_str(r"\infty")?,
+                other => write!(f, r"\mathrm{{{}}}", other)?,
+            }
+            let name = match self.units.name(level, term.n) {
+                Some(name) => name,
+                None => continue,
+            };
+            if !term.is_numeric() {
+                f.write_str(r"\,")?;
+            }
+            match (name, self.bold) {
+                (UnitName::Letters(name), false) => f.write_str(name)?,
+                (UnitName::Letters(name), true) => write!(f, r"\mathbf{{{}}}", name)?,
+                (UnitName::Indexed(n), false) => write!(f, "e_{{{}}}", n)?,
+                (UnitName::Indexed(n), true) => write!(f, r"\mathbf{{e}}_{{{}}}", n)?,
             }
-        } else if (type_name::<T>() == type_name::<Complex<f64>>())
-            || (type_name::<T>() == type_name::<Complex<f32>>())
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> fmt::Display for MathMl<'a, T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = Complex::<T>::LEVEL;
+        let variant = if self.bold {
+            " mathvariant=\"bold\""
+        } else {
+            ""
+        };
+
+        f.write_str("<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>")?;
+        for (position, term) in terms(self.z, self.all_terms, f, display_component)
+            .iter()
+            .enumerate()
         {
-            let real = self.re.to_string();
-            let imag = self.im.to_string();
+            match sign(position, term.negative, f).trim() {
+                "" => (),
+                sign => write!(f, "<mo>{}</mo>", sign)?,
+            }
+            match term.magnitude.as_str() {
+                _ if term.is_numeric() => write!(f, "<mn>{}</mn>", term.magnitude)?,
+                "inf" => f.write_str("<mi>&#x221E;</mi>")?,
+                other => write!(f, "<mi>{}</mi>", other)?,
+            }
+            match self.units.name(level, term.n) {
+                Some(UnitName::Letters(name)) => {
+                    write!(f, "<mo>&#x2062;</mo><mi{}>{}</mi>", variant, name)?
+                }
+                Some(UnitName::Indexed(n)) => write!(
+                    f,
+                    "<mo>&#x2062;</mo><msub><mi{}>e</mi><mn>{}</mn></msub>",
+                    variant, n
+                )?,
+                None => (),
+            }
+        }
+        f.write_str("</mrow></math>")
+    }
+}
+
+/// How `PolarForm` writes a number.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum PolarStyle {
+    /// `r·exp(θi)`, or with the axis of quaternions and above, like
+    /// `r·exp(θk)` or `r·exp(θ·(0.6j + 0.8k))`.
+    #[default]
+    Exponential,
+    /// `r∠θ` for complex numbers, followed by the axis for quaternions and
+    /// above, like `r∠θk` or `r∠θ·(0.6j + 0.8k)`.
+    Angle,
+}
 
-            let real_split: Vec<&str> = real.split_whitespace().collect();
-            let imag_split: Vec<&str> = imag.split_whitespace().collect();
+/// Prints a complex or hypercomplex number in polar form, as its magnitude
+/// `r` times the exponential of the angle `θ` along a unit imaginary axis,
+/// created by `Complex::display_polar`. The axis is oriented so that its
+/// component along `i` is not negative, which puts the angle of complex
+/// numbers in (-π, π], and real numbers lie on the axis `i`. The precision
+/// of the formatter applies to every number, and the output can be parsed
+/// back.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use complex::fmt::PolarStyle;
+///
+/// let z = complex![0., -2.];
+/// let q = complex![0., 0., 1.2, 1.6];
+///
+/// assert_eq!(format!("{:.4}", z.display_polar()), "2.0000·exp(-1.5708i)");
+/// assert_eq!(
+///     format!("{:.2}", z.display_polar().style(PolarStyle::Angle)),
+///     "2.00∠-1.57"
+/// );
+/// assert_eq!(
+///     format!("{:.2}", q.display_polar()),
+///     "2.00·exp(1.57·(0.60j + 0.80k))"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PolarForm<'a, T> {
+    z: &'a Complex<T>,
+    style: PolarStyle,
+    units: Units,
+}
 
-            let len = imag_split[2].len();
+impl<'a, T> PolarForm<'a, T> {
+    /// Selects between the exponential and the angle form.
+    pub fn style(self, style: PolarStyle) -> Self {
+        Self { style, ..self }
+    }
 
-            if &imag_split[0][0..1] != "-" {
-                return write!(
-                    f,
-                    "{} {} {} + {}j {} {}k",
-                    &real_split[0],
-                    &real_split[1],
-                    &real_split[2],
-                    &imag_split[0],
-                    &imag_split[1],
-                    &imag_split[2][..(len - 1)]
-                );
+    /// Selects the names of the imaginary units of the axis.
+    pub fn units(self, units: Units) -> Self {
+        Self { units, ..self }
+    }
+}
+
+impl<'a, T> fmt::Display for PolarForm<'a, T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::Display,
+    Complex<T>: Identity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let zero = <T::Scalar as Identity>::zero();
+        let one = <T::Scalar as Identity>::one();
+        let x = self.z.as_slice();
+        let norm = x[1..].iter().fold(zero, |acc, y| acc.hypot(*y));
+        let mut theta = norm.atan2(x[0]);
+        let mut axis = Complex::<T>::zero();
+
+        if norm == zero {
+            axis[1] = one;
+        } else {
+            let norm = if x[1] < zero {
+                theta = -theta;
+                -norm
             } else {
-                return write!(
-                    f,
-                    "{} {} {} - {}j {} {}k",
-                    &real_split[0],
-                    &real_split[1],
-                    &real_split[2],
-                    &imag_split[0][1..],
-                    &imag_split[1],
-                    &imag_split[2][..(len - 1)]
-                );
+                norm
+            };
+            for (n, y) in x.iter().enumerate().skip(1) {
+                axis[n] = *y / norm;
             }
-        } else {
-            return write!(f, "({}, {})", &self.re, &self.im);
         }
+
+        write!(f, "{}", display_component(&x[0].hypot(norm), f))?;
+        match self.style {
+            PolarStyle::Exponential => f.write_str("·exp(")?,
+            PolarStyle::Angle => f.write_char('∠')?,
+        }
+        let theta = display_component(&theta, f);
+        f.write_str(&theta)?;
+
+        let level = Complex::<T>::LEVEL;
+        let mut units = axis.components().enumerate().filter(|(_, y)| **y != zero);
+        match (units.next(), units.next()) {
+            (Some((1, _)), None) if level == 1 && self.style == PolarStyle::Angle => (),
+            (Some((n, y)), None) if *y == one => {
+                // separates indexed units from the exponent of a number, as
+                // in `2*e1`, and other units from NaN and inf, as in `NaN*i`
+                match self.units.name(level, n) {
+                    Some(UnitName::Letters(name)) => {
+                        if !theta.ends_with(|c: char| c.is_ascii_digit() || c == '.') {
+                            f.write_char('*')?;
+                        }
+                        f.write_str(name)?
+                    }
+                    Some(UnitName::Indexed(n)) => write!(f, "*e{}", n)?,
+                    None => (),
+                }
+            }
+            _ => {
+                let axis = axis.notation().units(self.units);
+                match f.precision() {
+                    Some(p) => write!(f, "·({:.*})", p, axis)?,
+                    None => write!(f, "·({})", axis)?,
+                }
+            }
+        }
+        match self.style {
+            PolarStyle::Exponential => f.write_char(')'),
+            PolarStyle::Angle => Ok(()),
+        }
+    }
+}
+
+macro_rules! format_component {
+    ($name:ident, $imp:ident, $plain:literal, $alternate:literal, $precise:literal,
+     $precise_alternate:literal) => {
+        /// Formats a single component with the precision and alternate flag of
+        /// the formatter, leaving width, fill and sign to the caller.
+        fn $name<S: fmt::$imp>(x: &S, f: &fmt::Formatter<'_>) -> String {
+            match (f.precision(), f.alternate()) {
+                (None, false) => format!($plain, x),
+                (None, true) => format!($alternate, x),
+                (Some(p), false) => format!($precise, p, x),
+                (Some(p), true) => format!($precise_alternate, p, x),
+            }
+        }
+    };
+}
+
+format_component!(display_component, Display, "{}", "{:#}", "{:.*}", "{:#.*}");
+format_component!(
+    upper_exp_component,
+    UpperExp,
+    "{:E}",
+    "{:#E}",
+    "{:.*E}",
+    "{:#.*E}"
+);
+format_component!(
+    lower_exp_component,
+    LowerExp,
+    "{:e}",
+    "{:#e}",
+    "{:.*e}",
+    "{:#.*e}"
+);
+
+/// Writes `sign` and `body`, padded to the width of the formatter with its
+/// fill and alignment, or with zeros after the sign for the `0` flag.
+fn pad(f: &mut fmt::Formatter<'_>, sign: &str, body: &str) -> fmt::Result {
+    let len = sign.chars().count() + body.chars().count();
+    let padding = match f.width() {
+        Some(width) if width > len => width - len,
+        _ => return write!(f, "{}{}", sign, body),
+    };
+    if f.sign_aware_zero_pad() {
+        f.write_str(sign)?;
+        for _ in 0..padding {
+            f.write_char('0')?;
+        }
+        return f.write_str(body);
+    }
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Right) | None => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
     }
+    write!(f, "{}{}", sign, body)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
 }
 
-#[derive(Debug, Clone)]
-pub struct ComplexParseError;
+/// A term of a printed number, with its component formatted without the sign.
+struct Term {
+    n: usize,
+    negative: bool,
+    magnitude: String,
+}
+
+impl Term {
+    /// Returns `true` if the magnitude is a number rather than NaN or inf.
+    fn is_numeric(&self) -> bool {
+        self.magnitude
+            .ends_with(|c: char| c.is_ascii_digit() || c == '.')
+    }
+}
+
+/// Formats the components of `z` with `component`. Terms with a positive zero
+/// are left out unless `all_terms` is set, and if every term is left out the
+/// real part is kept.
+fn terms<T>(
+    z: &Complex<T>,
+    all_terms: bool,
+    f: &fmt::Formatter<'_>,
+    component: fn(&T::Scalar, &fmt::Formatter<'_>) -> String,
+) -> Vec<Term>
+where
+    T: Hypercomplex,
+{
+    let zero = <T::Scalar as Identity>::zero();
+    let mut terms: Vec<Term> = z
+        .components()
+        .enumerate()
+        .filter_map(|(n, x)| {
+            let text = component(x, f);
+            let (negative, magnitude) = match text.strip_prefix('-') {
+                Some(magnitude) => (true, magnitude.to_string()),
+                None => (false, text.clone()),
+            };
+            if all_terms || negative || *x != zero {
+                Some(Term {
+                    n,
+                    negative,
+                    magnitude,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if terms.is_empty() {
+        terms.push(Term {
+            n: 0,
+            negative: false,
+            magnitude: component(&zero, f),
+        });
+    }
+    terms
+}
+
+/// Returns the sign written before a term at `position` in the sum.
+fn sign(position: usize, negative: bool, f: &fmt::Formatter<'_>) -> &'static str {
+    match (position, negative, f.sign_plus()) {
+        (0, true, _) => "-",
+        (0, false, true) => "+",
+        (0, false, false) => "",
+        (_, true, _) => " - ",
+        (_, false, _) => " + ",
+    }
+}
+
+impl<'a, T> Notation<'a, T>
+where
+    T: Hypercomplex,
+{
+    /// Writes the terms with each component formatted by `component` and then
+    /// padded, so that the flags of the formatter apply to every component.
+    fn write_terms(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        component: fn(&T::Scalar, &fmt::Formatter<'_>) -> String,
+    ) -> fmt::Result {
+        let level = Complex::<T>::LEVEL;
+
+        for (position, term) in terms(self.z, self.all_terms, f, component)
+            .iter()
+            .enumerate()
+        {
+            let sign = sign(position, term.negative, f);
+            if position == 0 {
+                pad(f, sign, &term.magnitude)?;
+            } else {
+                f.write_str(sign)?;
+                pad(f, "", &term.magnitude)?;
+            }
+            match self.units.name(level, term.n) {
+                // separates indexed units from the exponent of a number, as
+                // in `2*e1`, and other units from NaN and inf, as in `NaN*i`
+                Some(UnitName::Indexed(_)) => f.write_char('*')?,
+                Some(_) if !term.is_numeric() => f.write_char('*')?,
+                _ => (),
+            }
+            match self.units.name(level, term.n) {
+                Some(UnitName::Letters(name)) => f.write_str(name)?,
+                Some(UnitName::Indexed(n)) => write!(f, "e{}", n)?,
+                None => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> fmt::Display for Notation<'a, T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_terms(f, display_component)
+    }
+}
+
+impl<'a, T> fmt::LowerExp for Notation<'a, T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::LowerExp,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_terms(f, lower_exp_component)
+    }
+}
+
+impl<'a, T> fmt::UpperExp for Notation<'a, T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::UpperExp,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_terms(f, upper_exp_component)
+    }
+}
+
+/// Prints a number as a flat sum like `1 + 2i + 3j + 4k`, with the units of
+/// `Units::Standard`. Precision, width, fill, alignment and the `0` and `#`
+/// flags apply to every component, while the `+` flag shows the sign of the
+/// first term. Terms with a positive zero are left out, and indexed units and
+/// NaN and infinite components are separated by `*`, as in `2*e1` and
+/// `NaN*i`, so that every number can be parsed back.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+///
+/// let o = complex![1., 2., 3., 4., 5., 6., 7., -8.];
+/// let q = complex![1., -2., 3., 4.5];
+///
+/// assert_eq!(q.to_string(), "1 - 2i + 3j + 4.5k");
+/// assert_eq!(complex![0., -0., f64::INFINITY, 0.].to_string(), "-0i + inf*j");
+/// assert_eq!(o.to_string(), "1 + 2*e1 + 3*e2 + 4*e3 + 5*e4 + 6*e5 + 7*e6 - 8*e7");
+/// assert_eq!(format!("{:+.2}", q), "+1.00 - 2.00i + 3.00j + 4.50k");
+/// assert_eq!(format!("{:5}", q), "    1 -     2i +     3j +   4.5k");
+/// ```
+impl<T> fmt::Display for Complex<T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.notation(), f)
+    }
+}
+
+/// Prints a number as a flat sum with every component in scientific notation,
+/// like `1.5e3 - 2e-1i`, which parses back. Flags apply like for `Display`.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+///
+/// let z = complex![1500., -0.2];
+///
+/// assert_eq!(format!("{:e}", z), "1.5e3 - 2e-1i");
+/// assert_eq!(format!("{:.2e}", z).parse::<Complexf64>(), Ok(z));
+/// ```
+impl<T> fmt::LowerExp for Complex<T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::LowerExp,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerExp::fmt(&self.notation(), f)
+    }
+}
+
+/// Prints a number as a flat sum with every component in scientific notation,
+/// like `1.5E3 - 2E-1i`. Flags apply like for `Display`.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+///
+/// let q = complex![1500., -0.2, 0., 1.];
+///
+/// assert_eq!(format!("{:E}", q), "1.5E3 - 2E-1i + 1E0k");
+/// ```
+impl<T> fmt::UpperExp for Complex<T>
+where
+    T: Hypercomplex,
+    T::Scalar: fmt::UpperExp,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperExp::fmt(&self.notation(), f)
+    }
+}
+
+/// The reason a string could not be parsed into a complex or hypercomplex
+/// number.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A character that cannot appear at this position.
+    UnexpectedCharacter,
+    /// The input ended where a number was expected.
+    MissingComponent,
+    /// A number too large to be represented by the scalar type.
+    FloatOverflow,
+    /// A unit or nesting that does not exist for the dimension of the type.
+    WrongDimension,
+    /// A component that was already given by an earlier term.
+    DuplicateComponent,
+    /// An axis of a number in polar form that is zero or not imaginary.
+    InvalidAxis,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            ParseErrorKind::UnexpectedCharacter => "unexpected character",
+            ParseErrorKind::MissingComponent => "missing component",
+            ParseErrorKind::FloatOverflow => "number out of range",
+            ParseErrorKind::WrongDimension => "wrong dimension",
+            ParseErrorKind::DuplicateComponent => "duplicate component",
+            ParseErrorKind::InvalidAxis => "invalid axis",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+/// Error returned when parsing a complex or hypercomplex number fails.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use complex::fmt::ParseErrorKind;
+///
+/// let err = "4b+1i".parse::<Complexf64>().unwrap_err();
+///
+/// assert_eq!(err.offset, 1);
+/// assert_eq!(err.token, "b");
+/// assert_eq!(err.kind, ParseErrorKind::UnexpectedCharacter);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ComplexParseError {
+    /// Byte offset of the offending token in the input.
+    pub offset: usize,
+    /// The offending token, empty if the input ended too early.
+    pub token: String,
+    /// The reason parsing failed.
+    pub kind: ParseErrorKind,
+}
 
 impl fmt::Display for ComplexParseError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Invalid format for a complex or hypercomplex type.")
+        if self.token.is_empty() {
+            write!(
+                f,
+                "Invalid format for a complex or hypercomplex type: {} at end of input.",
+                self.kind
+            )
+        } else {
+            write!(
+                f,
+                "Invalid format for a complex or hypercomplex type: {} `{}` at byte {}.",
+                self.kind, self.token, self.offset
+            )
+        }
+    }
+}
+
+impl std::error::Error for ComplexParseError {}
+
+/// Recursive descent parser over flat sums of terms like `a + bi + cj + dk`
+/// or `a + b*e1 + c e2 + ...`, the nested pairs `(x, y)` of octonions and
+/// above, and the polar forms `r·exp(θu)` and `r∠θu`.
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { s, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.s[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.pos;
+        while let Some('0'..='9') = self.peek() {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn error(&self, start: usize, kind: ParseErrorKind) -> ComplexParseError {
+        ComplexParseError {
+            offset: start,
+            token: self.s[start..self.pos].to_string(),
+            kind,
+        }
+    }
+
+    /// Error for the character at the current position, which cannot start
+    /// what is expected there.
+    fn unexpected(&self) -> ComplexParseError {
+        match self.peek() {
+            Some(c) => ComplexParseError {
+                offset: self.pos,
+                token: c.to_string(),
+                kind: ParseErrorKind::UnexpectedCharacter,
+            },
+            None => self.error(self.pos, ParseErrorKind::MissingComponent),
+        }
+    }
+
+    /// Skips `c` if it is at the current position.
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ComplexParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ComplexParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    /// Scans `inf`, `infinity` or `NaN` in any case.
+    fn special(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        let rest = &self.s[start..];
+        let len =
+            ["infinity", "inf", "nan"]
+                .iter()
+                .find_map(|name| match rest.get(..name.len()) {
+                    Some(head) if head.eq_ignore_ascii_case(name) => Some(name.len()),
+                    _ => None,
+                })?;
+        self.pos += len;
+        Some(&self.s[start..self.pos])
+    }
+
+    /// Scans an unsigned decimal number with an optional exponent.
+    fn number(&mut self) -> Result<&'a str, ComplexParseError> {
+        let start = self.pos;
+        let mut digits = self.skip_digits();
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            digits += self.skip_digits();
+        }
+        if digits == 0 {
+            self.pos = start;
+            return Err(self.unexpected());
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if let Some('+') | Some('-') = self.peek() {
+                self.pos += 1;
+            }
+            if self.skip_digits() == 0 {
+                self.pos = mantissa_end;
+            }
+        }
+        Ok(&self.s[start..self.pos])
+    }
+
+    /// Scans the digits of an indexed unit `e1`, `e2`, ... after the `e`.
+    fn unit_index(&mut self) -> usize {
+        let start = self.pos;
+        self.skip_digits();
+        self.s[start..self.pos].parse().unwrap_or(usize::MAX)
+    }
+
+    /// Scans an optional imaginary unit, returning its component index, or
+    /// `None` if the unit does not exist at the given level. Indexed units
+    /// are only read when `separated` from their coefficient by `*` or
+    /// whitespace, since `2e1` is the number twenty.
+    fn unit(&mut self, level: u32, separated: bool) -> Option<usize> {
+        let index = match self.peek() {
+            Some('e') if !separated => return None,
+            Some('e') => {
+                self.pos += 1;
+                return Some(self.unit_index());
+            }
+            Some('i') | Some('I') => 1,
+            Some('j') | Some('J') => 2,
+            Some('k') | Some('K') => 3,
+            Some('l') | Some('L') => 0,
+            _ => return Some(0),
+        };
+        self.pos += 1;
+        if index != 0 {
+            if let Some('l') | Some('L') = self.peek() {
+                self.pos += 1;
+            } else {
+                return Some(index);
+            }
+        }
+        // the octonion units l, il, jl and kl
+        if level == 3 {
+            Some(index + 4)
+        } else {
+            None
+        }
+    }
+
+    /// Converts a number scanned from `start` to the scalar type, which must be
+    /// finite unless it is one of the special values.
+    fn scalar<S: Scalar + FromStr>(
+        start: usize,
+        text: &str,
+        special: bool,
+    ) -> Result<S, ComplexParseError> {
+        let error = |kind| ComplexParseError {
+            offset: start,
+            token: text.to_string(),
+            kind,
+        };
+        let x = text
+            .parse::<S>()
+            .map_err(|_| error(ParseErrorKind::UnexpectedCharacter))?;
+        if special || x.is_finite() {
+            Ok(x)
+        } else {
+            Err(error(ParseErrorKind::FloatOverflow))
+        }
+    }
+
+    /// Parses signed terms into `out`, stopping before anything that cannot
+    /// continue the sum.
+    fn terms<S: Scalar + FromStr>(
+        &mut self,
+        level: u32,
+        out: &mut [S],
+    ) -> Result<(), ComplexParseError> {
+        let mut seen = vec![false; out.len()];
+        let mut first = true;
+
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            let negative = match self.peek() {
+                Some(sign @ '+') | Some(sign @ '-') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    sign == '-'
+                }
+                _ if first => false,
+                _ => return Ok(()),
+            };
+            let number_start = self.pos;
+            let (number, special) = match self.special() {
+                Some(number) => (number, true),
+                None => (self.number()?, false),
+            };
+            let number_end = self.pos;
+            self.skip_whitespace();
+            if self.eat('*') {
+                self.skip_whitespace();
+            }
+            let separated = self.pos > number_end;
+            let index = match self.unit(level, separated) {
+                Some(index) if index < out.len() => index,
+                _ => return Err(self.error(start, ParseErrorKind::WrongDimension)),
+            };
+            if seen[index] {
+                return Err(self.error(start, ParseErrorKind::DuplicateComponent));
+            }
+            let x = Self::scalar::<S>(number_start, number, special)?;
+
+            out[index] = if negative { -x } else { x };
+            seen[index] = true;
+            first = false;
+        }
+    }
+
+    /// Parses a number in polar form, `r·exp(θu)` or `r∠θu`, into `out`. The
+    /// axis `u` is a unit like `k`, or a sum in parentheses like `·(j + k)`
+    /// that is normalized, and may be left out after `∠` for the axis `i`.
+    /// Returns `false` without consuming anything if the input does not start
+    /// in polar form.
+    fn polar<S: Scalar + FromStr>(
+        &mut self,
+        level: u32,
+        out: &mut [S],
+    ) -> Result<bool, ComplexParseError> {
+        let start = self.pos;
+        self.skip_whitespace();
+        let r_start = self.pos;
+        let (r, special) = match self.special() {
+            Some(r) => (r, true),
+            None => match self.number() {
+                Ok(r) => (r, false),
+                Err(_) => {
+                    self.pos = start;
+                    return Ok(false);
+                }
+            },
+        };
+        self.skip_whitespace();
+        let exponential = if self.eat('∠') {
+            false
+        } else {
+            if !self.eat('·') {
+                self.eat('*');
+            }
+            self.skip_whitespace();
+            if !self.s[self.pos..].starts_with("exp") {
+                self.pos = start;
+                return Ok(false);
+            }
+            self.pos += 3;
+            self.expect('(')?;
+            true
+        };
+        let r = Self::scalar::<S>(r_start, r, special)?;
+
+        self.skip_whitespace();
+        let theta_start = self.pos;
+        let negative = if self.eat('-') {
+            true
+        } else {
+            self.eat('+');
+            false
+        };
+        let number_start = self.pos;
+        let theta = match self.special() {
+            Some(theta) => Self::scalar::<S>(number_start, theta, true)?,
+            None => Self::scalar::<S>(number_start, self.number()?, false)?,
+        };
+        let theta = if negative { -theta } else { theta };
+
+        let mut axis = vec![S::zero(); out.len()];
+        let separated = self.eat('·') || self.eat('*');
+        let axis_start = self.pos;
+        if separated && self.peek() == Some('(') {
+            self.pos += 1;
+            self.terms(level, &mut axis)?;
+            self.expect(')')?;
+        } else {
+            match self.unit(level, separated) {
+                Some(0) if !exponential && !separated => axis[1] = S::one(),
+                Some(0) => return Err(self.unexpected()),
+                Some(n) if n < out.len() => axis[n] = S::one(),
+                _ => return Err(self.error(theta_start, ParseErrorKind::WrongDimension)),
+            }
+        }
+        let norm = axis[1..].iter().fold(S::zero(), |acc, y| acc.hypot(*y));
+        if axis[0] != S::zero() || norm == S::zero() {
+            return Err(self.error(axis_start, ParseErrorKind::InvalidAxis));
+        }
+        if exponential {
+            self.expect(')')?;
+        }
+
+        let (sin, cos) = (theta.sin() * r, theta.cos() * r);
+        out[0] = cos;
+        for n in 1..out.len() {
+            out[n] = sin * axis[n] / norm;
+        }
+        Ok(true)
+    }
+
+    /// Parses a number of the given Cayley-Dickson level into `out`.
+    fn value<S: Scalar + FromStr>(
+        &mut self,
+        level: u32,
+        out: &mut [S],
+    ) -> Result<(), ComplexParseError> {
+        self.skip_whitespace();
+        if level <= 2 || self.peek() != Some('(') {
+            return self.terms(level, out);
+        }
+        self.pos += 1;
+        let (re, im) = out.split_at_mut(out.len() / 2);
+        self.value(level - 1, re)?;
+        self.expect(',')?;
+        self.value(level - 1, im)?;
+        self.expect(')')
     }
 }
 
 impl<T> FromStr for Complex<T>
 where
-    T: FromStr + Identity,
+    T: Hypercomplex,
+    T::Scalar: FromStr,
+    Complex<T>: Identity,
 {
     type Err = ComplexParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let result: Result<Self, Self::Err>;
-        if (type_name::<T>() == "f32") || (type_name::<T>() == "f64") {
-            let float_str = r"^([+-]?(?:\d+|\d*\.\d+|\d+\.\d*)(?:[eE][+-]?\d{1,4})?)?";
-            let float_imag_str = r"(?:([+-]?(?:\d+|\d*\.\d+|\d+\.\d*)(?:[eE][+-]?\d{1,4})?)[iI])?$";
-            let pattern = float_str.to_string() + &float_imag_str;
-
-            let re = Regex::new(&pattern[..]).unwrap();
-            let caps = re.captures(s).unwrap();
-            let textx = caps.get(1).map_or("", |m| m.as_str());
-            let texty = caps.get(2).map_or("", |m| m.as_str());
-
-            let x = textx.parse::<T>();
-            let y = texty.parse::<T>();
-
-            result = match (x, y) {
-                (Ok(re), Ok(im)) => Ok(Self { re: re, im: im }),
-                (Ok(re), Err(_)) => Ok(Self {
-                    re: re,
-                    im: <T as Identity>::zero(),
-                }),
-                (Err(_), Ok(im)) => Ok(Self {
-                    re: <T as Identity>::zero(),
-                    im: im,
-                }),
-                (Err(_), Err(_)) => Err(ComplexParseError),
-            };
-        } else if (type_name::<T>() == type_name::<Complex<f64>>())
-            || (type_name::<T>() == type_name::<Complex<f32>>())
-        {
-            let float_str = r"^([+-]?(?:\d+|\d*\.\d+|\d+\.\d*)(?:[eE][+-]?\d{1,4})?)?";
-            let float_imag_str_i =
-                r"(?:([+-]?(?:\d+|\d*\.\d+|\d+\.\d*)(?:[eE][+-]?\d{1,4})?)[iI])?";
-            let float_imag_str_j =
-                r"(?:([+-]?(?:\d+|\d*\.\d+|\d+\.\d*)(?:[eE][+-]?\d{1,4})?)[jJ])?";
-            let float_imag_str_k =
-                r"(?:([+-]?(?:\d+|\d*\.\d+|\d+\.\d*)(?:[eE][+-]?\d{1,4})?)[kK])?$";
-            let pattern =
-                float_str.to_string() + &float_imag_str_i + &float_imag_str_j + &float_imag_str_k;
-
-            let re = Regex::new(&pattern[..]).unwrap();
-            let caps = re.captures(s).unwrap();
-            let textx = caps.get(1).map_or("", |m| m.as_str());
-            let texty = caps.get(2).map_or("", |m| m.as_str());
-            let textu = caps.get(3).map_or("", |m| m.as_str());
-            let textv = caps.get(4).map_or("", |m| m.as_str());
-
-            let mut zstring = String::new();
-            if (textx != "") && (texty != "") {
-                let temp_str = textx.to_string() + &texty + &"i";
-                zstring.push_str(&temp_str[..]);
-            } else if textx != "" {
-                let temp_str = textx.to_string();
-                zstring.push_str(&temp_str[..]);
-            } else if texty != "" {
-                let temp_str = texty.to_string() + &"i";
-                zstring.push_str(&temp_str[..]);
-            }
-
-            let mut wstring = String::new();
-            if (textu != "") && (textv != "") {
-                let temp_str = textu.to_string() + &textv + &"i";
-                wstring.push_str(&temp_str[..]);
-            } else if textu != "" {
-                let temp_str = textu.to_string();
-                wstring.push_str(&temp_str[..]);
-            } else if textv != "" {
-                let temp_str = textv.to_string() + &"i";
-                wstring.push_str(&temp_str[..]);
-            }
-
-            result = match (zstring.parse::<T>(), wstring.parse::<T>()) {
-                (Ok(re), Ok(im)) => Ok(Self { re: re, im: im }),
-                (Ok(re), Err(_)) => Ok(Self {
-                    re: re,
-                    im: <T as Identity>::zero(),
-                }),
-                (Err(_), Ok(im)) => Ok(Self {
-                    re: <T as Identity>::zero(),
-                    im: im,
-                }),
-                (Err(_), Err(_)) => Err(ComplexParseError),
-            };
-        } else {
-            let pattern = r"^\(\s*(.+)\s*,\s*(.+)\s*\)$";
-
-            let re = Regex::new(&pattern[..]).unwrap();
-            let caps = re.captures(s).unwrap();
-            let textx = caps.get(1).map_or("", |m| m.as_str());
-            let texty = caps.get(2).map_or("", |m| m.as_str());
-
-            result = match (textx.parse::<T>(), texty.parse::<T>()) {
-                (Ok(re), Ok(im)) => Ok(Self { re: re, im: im }),
-                (Ok(re), Err(_)) => Ok(Self {
-                    re: re,
-                    im: <T as Identity>::zero(),
-                }),
-                (Err(_), Ok(im)) => Ok(Self {
-                    re: <T as Identity>::zero(),
-                    im: im,
-                }),
-                (Err(_), Err(_)) => Err(ComplexParseError),
-            };
+        let mut z = Self::zero();
+        let mut parser = Parser::new(s);
+
+        if !parser.polar(Self::LEVEL, z.as_mut_slice())? {
+            parser.value(Self::LEVEL, z.as_mut_slice())?;
         }
+        parser.end()?;
 
-        result
+        Ok(z)
     }
 }
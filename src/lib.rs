@@ -4,14 +4,29 @@
 //! for hypercomplex numbers through a recursive construction. This crate
 //! allows any hypercomplex numbers to be manipulated with standard operators
 //! in a convenient manner.
-use std::any::type_name;
-use std::iter::{Product, Sum};
+use std::convert::TryFrom;
+use std::iter::{Map, Product, Sum};
 use std::ops::{
-    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Range, Rem, RemAssign,
+    Sub, SubAssign,
 };
 
+pub mod bytes;
+pub mod dual;
 pub mod fmt;
+pub mod npy;
 pub mod ops;
+mod plane;
+pub mod rotation;
+pub mod scalar;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod statistics;
+pub mod table;
+
+pub use dual::{DualQuaternion, DualQuaternionf32, DualQuaternionf64};
+use plane::{ImaginaryAxis, PrincipalBranch};
+pub use scalar::Scalar;
 
 /// Generates a corresponding `Complex<T>` from floating point numbers either
 /// `f32` or `f64` in groupings of powers of the number two.
@@ -27,6 +42,15 @@ pub mod ops;
 ///
 /// assert_eq!(quaternion, Complex::<Complex<f64>>::new(z1, z2));
 /// ```
+///
+/// The number of arguments must be a power of two, anything else is rejected
+/// at compile time.
+///
+/// ```compile_fail
+/// use complex::*;
+///
+/// let z = complex![1., 2., 3.];
+/// ```
 #[macro_export]
 macro_rules! complex{
     ( $x:expr, $y:expr ) => {
@@ -43,6 +67,11 @@ macro_rules! complex{
             ]
         }
     };
+    ( $( $x:expr ),* ) => {
+        compile_error!(
+            "complex! expects a power of two number of components, like 2, 4 or 8"
+        )
+    };
 }
 
 /// An alias for `Complex<f64>`, implements complex numbers with `f64`.
@@ -69,9 +98,21 @@ pub type Trigintaduonionf32 = Complex<Complex<Complex<Complex<Complex<f32>>>>>;
 
 /// Base struct that all complex and hypercomplex types are based off of
 /// recursively putting `Complex<T>` within itself for other hypercomplex types
-/// like `Complex<Complex<...>>`. `Complex<T>` can only be built out from f32
-/// and f64 at the very root of the structure.
+/// like `Complex<Complex<...>>`. `Complex<T>` can only be built out from a
+/// `Scalar`, such as f32 and f64, at the very root of the structure.
+///
+/// The layout is `repr(C)`, so every level is stored as its `DIM` scalar
+/// components in the order used by `Fill::from_slice`, without padding.
+///
+/// With the `serde` feature `Complex<T>` serializes as the nested `{re, im}`
+/// structure, see the `serialize` module for the flat representation.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[repr(C)]
 pub struct Complex<T> {
     pub re: T,
     pub im: T,
@@ -101,6 +142,139 @@ where
     }
 }
 
+impl<T> Complex<T>
+where
+    T: Hypercomplex,
+{
+    /// Views the components as a slice, in the order used by
+    /// `Fill::from_slice`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![1., 2., 3., 4.];
+    ///
+    /// assert_eq!(q.as_slice(), &[1., 2., 3., 4.]);
+    /// ```
+    pub fn as_slice(&self) -> &[T::Scalar] {
+        scalars(self)
+    }
+
+    /// Views the components as a mutable slice, in the order used by
+    /// `Fill::from_slice`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let mut q = complex![1., 2., 3., 4.];
+    /// q.as_mut_slice()[1..].iter_mut().for_each(|x| *x = -*x);
+    ///
+    /// assert_eq!(q, complex![1., -2., -3., -4.]);
+    /// ```
+    pub fn as_mut_slice(&mut self) -> &mut [T::Scalar] {
+        scalars_mut(self)
+    }
+
+    /// Iterates over the components, in the order used by `Fill::from_slice`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let o = complex![1., 2., 3., 4., 5., 6., 7., 8.];
+    /// let norm_sq: f64 = o.components().map(|x| x * x).sum();
+    ///
+    /// assert_eq!(norm_sq, o.abs_sq());
+    /// ```
+    pub fn components(&self) -> std::slice::Iter<'_, T::Scalar> {
+        self.as_slice().iter()
+    }
+
+    /// Creates a hypercomplex number from a slice of its components, failing
+    /// if the length of the slice is not `DIM`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = Quaternionf64::try_from_slice(&[1., 2., 3., 4.]);
+    /// let err = Quaternionf64::try_from_slice(&[1., 2., 3.]).unwrap_err();
+    ///
+    /// assert_eq!(q, Ok(complex![1., 2., 3., 4.]));
+    /// assert_eq!((err.expected, err.actual), (4, 3));
+    /// ```
+    pub fn try_from_slice(v: &[T::Scalar]) -> Result<Self, DimensionError>
+    where
+        Self: Identity,
+    {
+        if v.len() != Self::DIM {
+            return Err(DimensionError {
+                expected: Self::DIM,
+                actual: v.len(),
+            });
+        }
+        let mut z = Self::zero();
+        z.as_mut_slice().copy_from_slice(v);
+
+        Ok(z)
+    }
+
+    /// Copies the components into a vector, the inverse of `Fill::from_vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![1., 2., 3., 4.];
+    ///
+    /// assert_eq!(Quaternionf64::from_vec(q.to_vec()), q);
+    /// ```
+    pub fn to_vec(&self) -> Vec<T::Scalar> {
+        self.as_slice().to_vec()
+    }
+
+    /// Copies the components into an array, whose length `N` must equal
+    /// `DIM`, which is checked at compile time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![1., 2., 3., 4.];
+    /// let [w, x, y, z] = q.to_array();
+    ///
+    /// assert_eq!((w, x, y, z), (1., 2., 3., 4.));
+    /// ```
+    ///
+    /// An array of another length is rejected at compile time.
+    ///
+    /// ```compile_fail
+    /// use complex::*;
+    ///
+    /// let [w, x, y] = complex![1., 2., 3., 4.].to_array();
+    /// ```
+    pub fn to_array<const N: usize>(&self) -> [T::Scalar; N] {
+        let () = ArrayLength::<Self, N>::EQUALS_DIM;
+        std::array::from_fn(|n| self.as_slice()[n])
+    }
+}
+
+/// Checks the length `N` of an array of the components of `H` when a function
+/// using `EQUALS_DIM` is compiled.
+struct ArrayLength<H, const N: usize>(std::marker::PhantomData<H>);
+
+impl<H: Hypercomplex, const N: usize> ArrayLength<H, N> {
+    const EQUALS_DIM: () = assert!(N == H::DIM, "array length must equal the dimension");
+}
+
 /// Implements several common functions for complex and hypercomplex types.
 pub trait Functions<U, V> {
     /// Returns the exponent of a hypercomplex number.
@@ -117,8 +291,10 @@ pub trait Functions<U, V> {
     /// assert_eq!(expz, complex![PI.cos(), PI.sin()]);
     /// ```
     fn exp(&self) -> Self;
-    /// Returns the natural logarithm of a hypercomplex number. It's unique
-    /// up to integer multiples of 2π times some imaginary.
+    /// Returns the principal natural logarithm of a hypercomplex number. It's
+    /// unique up to integer multiples of 2π times its unit imaginary, see
+    /// `MultiValued` for the other branches. Negative reals are given the
+    /// logarithm along the first imaginary `i`.
     ///
     /// # Example
     ///
@@ -129,7 +305,7 @@ pub trait Functions<U, V> {
     /// let z = complex![-1.0, 0.0];
     /// let lnz = z.ln();
     ///
-    /// assert!((lnz - complex![0.0, 0.0]).abs_sq() < 1e-10);
+    /// assert!((lnz - complex![0.0, PI]).abs_sq() < 1e-10);
     /// ```
     fn ln(&self) -> Self;
     /// Calculate a hypercomplex number to the power of a floating point.
@@ -198,6 +374,90 @@ pub trait Functions<U, V> {
     /// assert_eq!(w, complex![0.0, 1.0]);
     /// ```
     fn powi(&self, num: i32) -> Self;
+    /// Returns the principal square root of a hypercomplex number. The branch
+    /// cut lies along the negative real axis, and negative reals are given the
+    /// root along the first imaginary `i`. For floats this is the real square
+    /// root.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![-4.0, 0.0, 0.0, 0.0];
+    /// let w = complex![1.0, 2.0, -2.0, 0.5];
+    ///
+    /// assert_eq!(z.sqrt(), complex![0.0, 2.0, 0.0, 0.0]);
+    /// assert_eq!(Quaternionf64::zero().sqrt(), Quaternionf64::zero());
+    /// assert!((w.sqrt() * w.sqrt() - w).abs_sq() < 1e-10);
+    /// ```
+    fn sqrt(&self) -> Self;
+    /// Returns the principal cube root of a hypercomplex number, the root
+    /// whose angle to the real axis is at most π/3. For floats this is the
+    /// real cube root, so `(-8.0).cbrt()` is `-2.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![8.0, 0.0];
+    /// let w = complex![-1.0, 2.0, 0.5, 3.0];
+    ///
+    /// assert!((z.cbrt() - complex![2.0, 0.0]).abs_sq() < 1e-10);
+    /// assert!((w.cbrt().powu(3) - w).abs_sq() < 1e-10);
+    /// ```
+    fn cbrt(&self) -> Self;
+    /// Returns the principal n-th root of a hypercomplex number, the root
+    /// whose angle to the real axis is at most π/n. For floats this is the
+    /// real n-th root, which exists for negative numbers when `n` is odd.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![0.0, 1.0];
+    /// let w = complex![-1.0, 2.0, 0.5, 3.0];
+    ///
+    /// assert!((z.nth_root(4).powu(4) - z).abs_sq() < 1e-10);
+    /// assert!((w.nth_root(5).powu(5) - w).abs_sq() < 1e-10);
+    /// ```
+    fn nth_root(&self, n: u32) -> Self;
+    /// Returns all n-th roots of a hypercomplex number, starting with the
+    /// principal root and turning counterclockwise in the complex plane of the
+    /// number, oriented as described for `Branch`. Floats return their roots
+    /// in the plane of the first imaginary `i`.
+    ///
+    /// A non-real number has exactly `n` roots, all in its own complex plane.
+    /// The roots of a negative real quaternion, octonion, etc. form whole
+    /// 2-spheres (6-spheres, ...) through every unit imaginary direction; only
+    /// those along the first imaginary `i` are returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![-1.0, 0.0, 0.0, 0.0];
+    /// let roots: Vec<Quaternionf64> = z.all_nth_roots(4);
+    ///
+    /// assert_eq!(roots.len(), 4);
+    /// assert!(roots.iter().all(|w| (w.powu(4) - z).abs_sq() < 1e-10));
+    /// assert!((roots[1] - complex![-0.5, 0.5, 0.0, 0.0] * 2.0_f64.sqrt()).abs_sq() < 1e-10);
+    ///
+    /// let cube_roots_of_one: Vec<Complexf64> = 1.0.all_nth_roots(3);
+    /// assert!((cube_roots_of_one[1] - complex![-0.5, 0.75_f64.sqrt()]).abs_sq() < 1e-10);
+    /// ```
+    fn all_nth_roots(&self, n: u32) -> Vec<V>;
     /// Returns the hyperbolic sine of a hypercomplex number.
     ///
     /// # Example
@@ -251,10 +511,503 @@ pub trait Functions<U, V> {
     /// let z = complex![0., PI];
     /// let w = z.sin();
     ///
-    /// assert!((w - Complex::<f64>::i() * PI.sinh()).abs_sq() < 1e-10);
+    /// assert!((w - Complex::<f64>::i() * PI.sinh()).abs_sq() < 1e-10);
+    /// ```
+    fn sin(&self) -> Self;
+    /// Returns the cosine of a hypercomplex number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let z = complex![0., PI];
+    /// let w = z.cos();
+    ///
+    /// assert!((w - Complex::<f64>::one() * PI.cosh()).abs_sq() < 1e-10);
+    /// ```
+    fn cos(&self) -> Self;
+    /// Returns the tangent of a hypercomplex number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let z = complex![0., PI];
+    /// let w = z.tan();
+    ///
+    /// assert!((w - Complex::<f64>::i() * PI.tanh()).abs_sq() < 1e-10);
+    /// ```
+    fn tan(&self) -> Self;
+    /// Returns the principal arcsine of a hypercomplex number. The branch cuts
+    /// lie on the real axis outside [-1, 1], and the real part of the result
+    /// lies in [-π/2, π/2]. Numbers on a cut are continued from the side of
+    /// the first imaginary `i`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let z = complex![0.5, 0.0, 0.0, 0.0];
+    /// let w = complex![0.3, 0.4];
+    ///
+    /// assert!((z.asin() - complex![0.5_f64.asin(), 0.0, 0.0, 0.0]).abs_sq() < 1e-10);
+    /// assert!((w.asin().sin() - w).abs_sq() < 1e-10);
+    /// assert!((complex![2.0, 0.0].asin().re - FRAC_PI_2).abs() < 1e-10);
+    /// ```
+    fn asin(&self) -> Self;
+    /// Returns the principal arccosine of a hypercomplex number. The branch
+    /// cuts lie on the real axis outside [-1, 1], and the real part of the
+    /// result lies in [0, π]. Numbers on a cut are continued from the side of
+    /// the first imaginary `i`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![0.3, 0.4];
+    ///
+    /// assert!((z.acos().cos() - z).abs_sq() < 1e-10);
+    /// assert!((complex![-1.0, 0.0].acos() - complex![std::f64::consts::PI, 0.0]).abs_sq() < 1e-10);
+    /// ```
+    fn acos(&self) -> Self;
+    /// Returns the principal arctangent of a hypercomplex number. The branch
+    /// cuts lie on the imaginary axis outside [-i, i], and the real part of
+    /// the result lies in [-π/2, π/2].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![1.0, 0.5];
+    ///
+    /// assert!((z.atan().tan() - z).abs_sq() < 1e-10);
+    /// assert!((complex![1.0, 0.0].atan() - complex![1.0_f64.atan(), 0.0]).abs_sq() < 1e-10);
+    /// ```
+    fn atan(&self) -> Self;
+    /// Returns the principal inverse hyperbolic sine of a hypercomplex number.
+    /// The branch cuts lie on the imaginary axis outside [-i, i], and the
+    /// imaginary part of the result lies in [-π/2, π/2].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![0.5, -1.0, 2.0, 0.25];
+    ///
+    /// assert!((z.asinh().sinh() - z).abs_sq() < 1e-10);
+    /// assert!((complex![2.0, 0.0].asinh() - complex![2.0_f64.asinh(), 0.0]).abs_sq() < 1e-10);
+    /// ```
+    fn asinh(&self) -> Self;
+    /// Returns the principal inverse hyperbolic cosine of a hypercomplex number.
+    /// The branch cut lies on the real axis below 1, and the imaginary part of
+    /// the result lies in [0, π] along the unit imaginary of the input. Numbers
+    /// on the cut are continued from the side of the first imaginary `i`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let z = complex![0.5, -1.0, 2.0, 0.25];
+    ///
+    /// assert!((z.acosh().cosh() - z).abs_sq() < 1e-10);
+    /// assert!((complex![0.5, 0.0].acosh() - complex![0.0, PI / 3.]).abs_sq() < 1e-10);
+    /// ```
+    fn acosh(&self) -> Self;
+    /// Returns the principal inverse hyperbolic tangent of a hypercomplex
+    /// number. The branch cuts lie on the real axis outside [-1, 1], and the
+    /// imaginary part of the result lies in [-π/2, π/2]. Numbers on a cut are
+    /// continued from the side of the first imaginary `i`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![0.5, -1.0, 2.0, 0.25];
+    ///
+    /// assert!((z.atanh().tanh() - z).abs_sq() < 1e-10);
+    /// assert!((complex![0.5, 0.0].atanh() - complex![0.5_f64.atanh(), 0.0]).abs_sq() < 1e-10);
+    /// ```
+    fn atanh(&self) -> Self;
+}
+
+impl<S, T> Functions<S, Complex<T>> for S
+where
+    S: Scalar,
+    Complex<T>: Mul<S, Output = Complex<T>>
+        + Add<S, Output = Complex<T>>
+        + ImaginaryConstants
+        + Copy
+        + Functions<S, Complex<T>>,
+{
+    fn exp(&self) -> Self {
+        Scalar::exp(*self)
+    }
+
+    fn ln(&self) -> Self {
+        Scalar::ln(*self)
+    }
+
+    fn powf(&self, num: Self) -> Self {
+        Scalar::powf(*self, num)
+    }
+
+    fn powz(&self, num: Complex<T>) -> Complex<T> {
+        (num * Scalar::ln(*self)).exp()
+    }
+
+    fn powu_tail(&self, num: u32, acc: Self) -> Self {
+        if num == 0 {
+            acc
+        } else if num % 2 == 0 {
+            let sq = *self * *self;
+            <Self as Functions<S, Complex<T>>>::powu_tail(&sq, num / 2, acc)
+        } else {
+            let sq = *self * *self;
+            <Self as Functions<S, Complex<T>>>::powu_tail(&sq, (num - 1) / 2, *self * acc)
+        }
+    }
+
+    fn powu(&self, num: u32) -> Self {
+        <Self as Functions<S, Complex<T>>>::powu_tail(self, num, S::one())
+    }
+
+    fn powi(&self, num: i32) -> Self {
+        Scalar::powi(*self, num)
+    }
+
+    fn sqrt(&self) -> Self {
+        Scalar::sqrt(*self)
+    }
+
+    fn cbrt(&self) -> Self {
+        Scalar::cbrt(*self)
+    }
+
+    fn nth_root(&self, n: u32) -> Self {
+        assert_ne!(n, 0, "the zeroth root is undefined");
+        let inv_n = S::one() / S::from_f64(n as f64);
+
+        if n % 2 == 1 && *self < S::zero() {
+            -Scalar::powf(-*self, inv_n)
+        } else {
+            Scalar::powf(*self, inv_n)
+        }
+    }
+
+    fn all_nth_roots(&self, n: u32) -> Vec<Complex<T>> {
+        assert_ne!(n, 0, "the zeroth root is undefined");
+        let i = <Complex<T> as ImaginaryConstants>::i();
+
+        Complex::new(*self, S::zero())
+            .nth_roots(n)
+            .into_iter()
+            .map(|w| i * w.im + w.re)
+            .collect()
+    }
+
+    fn sinh(&self) -> Self {
+        Scalar::sinh(*self)
+    }
+
+    fn cosh(&self) -> Self {
+        Scalar::cosh(*self)
+    }
+
+    fn tanh(&self) -> Self {
+        Scalar::tanh(*self)
+    }
+
+    fn sin(&self) -> Self {
+        Scalar::sin(*self)
+    }
+
+    fn cos(&self) -> Self {
+        Scalar::cos(*self)
+    }
+
+    fn tan(&self) -> Self {
+        Scalar::tan(*self)
+    }
+
+    fn asin(&self) -> Self {
+        Scalar::asin(*self)
+    }
+
+    fn acos(&self) -> Self {
+        Scalar::acos(*self)
+    }
+
+    fn atan(&self) -> Self {
+        Scalar::atan(*self)
+    }
+
+    fn asinh(&self) -> Self {
+        Scalar::asinh(*self)
+    }
+
+    fn acosh(&self) -> Self {
+        Scalar::acosh(*self)
+    }
+
+    fn atanh(&self) -> Self {
+        Scalar::atanh(*self)
+    }
+}
+
+impl<S, T> Functions<S, Complex<T>> for Complex<T>
+where
+    S: Scalar,
+    T: Conjugate
+        + AbsSq<S>
+        + Fill<S>
+        + Real<S>
+        + Copy
+        + Add<Output = T>
+        + Add<S, Output = T>
+        + Sub<Output = T>
+        + Sub<S, Output = T>
+        + Mul<Output = T>
+        + Mul<S, Output = T>
+        + Div<Output = T>
+        + Div<S, Output = T>
+        + Neg<Output = T>,
+    Complex<T>: Div<Output = Complex<T>> + Div<S, Output = Complex<T>> + ImaginaryConstants,
+{
+    fn exp(&self) -> Self {
+        let real = self.real();
+        let theta = (*self - real).abs();
+
+        Self::from_polar(real.exp(), theta, self.unit_imaginary())
+    }
+
+    fn ln(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_ln(), axis)
+    }
+
+    fn powf(&self, num: S) -> Self {
+        let ln_z = self.ln();
+
+        (ln_z * num).exp()
+    }
+
+    fn powz(&self, num: Self) -> Self {
+        let ln_z = self.ln();
+
+        (num * ln_z).exp()
+    }
+
+    fn powu_tail(&self, num: u32, acc: Self) -> Self {
+        if num == 0 {
+            acc
+        } else if num % 2 == 0 {
+            let sq = self * self;
+            <Self as Functions<S, Complex<T>>>::powu_tail(&sq, num / 2, acc)
+        } else {
+            let sq = self * self;
+            <Self as Functions<S, Complex<T>>>::powu_tail(&sq, (num - 1) / 2, self * acc)
+        }
+    }
+
+    fn powu(&self, num: u32) -> Self {
+        <Self as Functions<S, Complex<T>>>::powu_tail(self, num, Self::one())
+    }
+
+    fn powi(&self, num: i32) -> Self {
+        if num == 0 {
+            Self::zero()
+        } else if num < 0 {
+            let z = self.powu(-num as u32);
+            Self::one() / z
+        } else {
+            self.powu(num as u32)
+        }
+    }
+
+    fn sqrt(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_sqrt(), axis)
+    }
+
+    fn cbrt(&self) -> Self {
+        <Self as Functions<S, Complex<T>>>::nth_root(self, 3)
+    }
+
+    fn nth_root(&self, n: u32) -> Self {
+        assert_ne!(n, 0, "the zeroth root is undefined");
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_nth_root(n), axis)
+    }
+
+    fn all_nth_roots(&self, n: u32) -> Vec<Self> {
+        assert_ne!(n, 0, "the zeroth root is undefined");
+        let (w, axis) = self.split_axis();
+
+        w.nth_roots(n)
+            .into_iter()
+            .map(|root| Self::join_axis(root, axis))
+            .collect()
+    }
+
+    fn sinh(&self) -> Self {
+        let exp = self.exp();
+
+        (exp - Self::one() / exp) * S::from_f64(0.5)
+    }
+
+    fn cosh(&self) -> Self {
+        let exp = self.exp();
+
+        (exp + Self::one() / exp) * S::from_f64(0.5)
+    }
+
+    fn tanh(&self) -> Self {
+        let exp = self.exp();
+
+        (exp * exp - S::one()) / (exp * exp + S::one())
+    }
+
+    fn sin(&self) -> Self {
+        let i = <Self as ImaginaryConstants>::i();
+        let expiz = (i * *self).exp();
+
+        (expiz - Self::one() / expiz) * S::from_f64(0.5) * -i
+    }
+
+    fn cos(&self) -> Self {
+        let i = <Self as ImaginaryConstants>::i();
+        let expiz = (i * *self).exp();
+
+        (expiz + Self::one() / expiz) * S::from_f64(0.5)
+    }
+
+    fn tan(&self) -> Self {
+        let i = <Self as ImaginaryConstants>::i();
+        let expiz = (i * *self).exp();
+
+        (expiz * expiz - S::one()) / (i * expiz * expiz + i)
+    }
+
+    fn asin(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_asin(), axis)
+    }
+
+    fn acos(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_acos(), axis)
+    }
+
+    fn atan(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_atan(), axis)
+    }
+
+    fn asinh(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_asinh(), axis)
+    }
+
+    fn acosh(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_acosh(), axis)
+    }
+
+    fn atanh(&self) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.principal_atanh(), axis)
+    }
+}
+
+/// Selects a branch of the logarithm and of the powers and roots defined
+/// through it.
+///
+/// A hypercomplex number `z` with modulus `r` lies in the complex plane spanned
+/// by the real axis and its unit imaginary `u`, oriented so that the component
+/// of `u` along the first imaginary `i` is not negative. Its logarithms are
+/// `ln(r) + (θ + 2πk) u` for every integer `k`, where `θ` is the principal angle
+/// of `z` in that plane, lying in (-π, π]. For `Complex<f32>` and
+/// `Complex<f64>` this is the usual argument of a complex number.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Branch<U> {
+    /// The principal branch, with the angle in (-π, π].
+    Principal,
+    /// The k-th sheet, with the angle in (-π + 2πk, π + 2πk].
+    Sheet(i32),
+    /// The branch whose cut lies along the ray at the given angle `α`, with
+    /// the angle in (α - 2π, α].
+    Cut(U),
+}
+
+/// Evaluates the multivalued functions of complex and hypercomplex numbers on
+/// any branch of the logarithm.
+pub trait MultiValued<U>: Sized {
+    /// Returns the natural logarithm on the k-th sheet, `ln(z) + 2πk u` where
+    /// `u` is the oriented unit imaginary of the number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let z = complex![0.0, -1.0];
+    ///
+    /// assert!((z.ln_branch(0) - complex![0.0, -PI / 2.]).abs_sq() < 1e-10);
+    /// assert!((z.ln_branch(1) - complex![0.0, 3. * PI / 2.]).abs_sq() < 1e-10);
+    /// ```
+    fn ln_branch(&self, k: i32) -> Self;
+    /// Returns the natural logarithm on the selected branch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let z = complex![0.0, -1.0];
+    ///
+    /// assert_eq!(z.ln_on(Branch::Principal), z.ln());
+    /// assert!((z.ln_on(Branch::Cut(2. * PI)) - complex![0.0, 3. * PI / 2.]).abs_sq() < 1e-10);
+    /// ```
+    fn ln_on(&self, branch: Branch<U>) -> Self;
+    /// Calculates a hypercomplex number to the power of a floating point on
+    /// the selected branch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![-1.0, 0.0];
+    ///
+    /// assert!((z.powf_on(0.5, Branch::Principal) - complex![0.0, 1.0]).abs_sq() < 1e-10);
+    /// assert!((z.powf_on(0.5, Branch::Sheet(1)) - complex![0.0, -1.0]).abs_sq() < 1e-10);
     /// ```
-    fn sin(&self) -> Self;
-    /// Returns the cosine of a hypercomplex number.
+    fn powf_on(&self, num: U, branch: Branch<U>) -> Self;
+    /// Calculates a hypercomplex number to the power of a hypercomplex number
+    /// on the selected branch.
     ///
     /// # Example
     ///
@@ -262,237 +1015,83 @@ pub trait Functions<U, V> {
     /// use complex::*;
     /// use std::f64::consts::PI;
     ///
-    /// let z = complex![0., PI];
-    /// let w = z.cos();
+    /// let i = Complexf64::i();
+    /// let w = i.powz_on(i, Branch::Sheet(1));
     ///
-    /// assert!((w - Complex::<f64>::one() * PI.cosh()).abs_sq() < 1e-10);
+    /// assert!((w - complex![(-5. * PI / 2.).exp(), 0.0]).abs_sq() < 1e-10);
     /// ```
-    fn cos(&self) -> Self;
-    /// Returns the tangent of a hypercomplex number.
+    fn powz_on(&self, num: Self, branch: Branch<U>) -> Self;
+    /// Returns the n-th root on the selected branch. The sheets `0..n` give
+    /// every root in the order of `Functions::all_nth_roots`, so for example
+    /// `nth_root_on(2, Branch::Sheet(1))` is the other square root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
     ///
     /// # Example
     ///
     /// ```
     /// use complex::*;
-    /// use std::f64::consts::PI;
     ///
-    /// let z = complex![0., PI];
-    /// let w = z.tan();
+    /// let z = complex![4.0, 0.0, 0.0, 0.0];
     ///
-    /// assert!((w - Complex::<f64>::i() * PI.tanh()).abs_sq() < 1e-10);
+    /// assert_eq!(z.nth_root_on(2, Branch::Principal), z.sqrt());
+    /// assert!((z.nth_root_on(2, Branch::Sheet(1)) + z.sqrt()).abs_sq() < 1e-10);
     /// ```
-    fn tan(&self) -> Self;
+    fn nth_root_on(&self, n: u32, branch: Branch<U>) -> Self;
 }
 
-macro_rules! impl_functions_for_float {
-    ($($u:ty),* ) => {
-        $(
-            impl<T> Functions<$u, Complex<T>> for $u
-            where Complex<T>: Mul<$u, Output=Complex<T>> + Functions<$u, Complex<T>>,
-            {
-                fn exp(&self) -> Self {
-                    Self::exp(*self)
-                }
-
-                fn ln(&self) -> Self {
-                    Self::ln(*self)
-                }
-
-                fn powf(&self, num: Self) -> Self {
-                    Self::powf(*self, num)
-                }
-
-                fn powz(&self, num: Complex<T>) -> Complex<T> {
-                    (num * Self::ln(*self)).exp()
-                }
-
-                fn powu_tail(&self, num: u32, acc: Self) -> Self {
-                    if num == 0 {
-                        return acc;
-                    } else if (num % 2) == 0 {
-                        let sq = self * self;
-                        return <Self as Functions<$u, Complex<T>>>::powu_tail(&sq, num / 2, acc);
-                    } else {
-                        let sq = self * self;
-                        return <Self as Functions<$u, Complex<T>>>::powu_tail(&sq, (num - 1) / 2, self * acc);
-                    }
-                }
-
-                fn powu(&self, num: u32) -> Self {
-                    <Self as Functions<$u, Complex<T>>>::powu_tail(self, num, Self::one())
-                }
-
-                fn powi(&self, num: i32) -> Self {
-                    Self::powi(*self, num)
-                }
-
-                fn sinh(&self) -> Self {
-                    Self::sinh(*self)
-                }
-
-                fn cosh(&self) -> Self {
-                    Self::cosh(*self)
-                }
-
-                fn tanh(&self) -> Self {
-                    Self::tanh(*self)
-                }
-
-                fn sin(&self) -> Self {
-                    Self::sin(*self)
-                }
-
-                fn cos(&self) -> Self {
-                    Self::cos(*self)
-                }
-
-                fn tan(&self) -> Self {
-                    Self::tan(*self)
-                }
-            }
+impl<S, T> MultiValued<S> for Complex<T>
+where
+    S: Scalar,
+    T: Conjugate
+        + AbsSq<S>
+        + Fill<S>
+        + Real<S>
+        + Copy
+        + Add<Output = T>
+        + Add<S, Output = T>
+        + Sub<Output = T>
+        + Sub<S, Output = T>
+        + Mul<Output = T>
+        + Mul<S, Output = T>
+        + Div<Output = T>
+        + Div<S, Output = T>
+        + Neg<Output = T>,
+    Complex<T>: Div<Output = Complex<T>> + Div<S, Output = Complex<T>> + ImaginaryConstants,
+{
+    fn ln_branch(&self, k: i32) -> Self {
+        self.ln_on(Branch::Sheet(k))
+    }
 
-            impl<T> Functions<$u, Complex<T>> for Complex<T>
-            where
-                T: Conjugate
-                    + AbsSq<$u>
-                    + Fill<$u>
-                    + Real<$u>
-                    + Copy
-                    + Add<Output = T>
-                    + Add<$u, Output = T>
-                    + Sub<Output = T>
-                    + Sub<$u, Output = T>
-                    + Mul<Output = T>
-                    + Mul<$u, Output = T>
-                    + Div<Output = T>
-                    + Div<$u, Output = T>
-                    + Neg<Output = T>,
-                Complex<T>:
-                    Div<Output = Complex<T>>
-                    + Div<$u, Output = Complex<T>>
-                    + ImaginaryConstants,
-            {
-                fn exp(&self) -> Self {
-                    let real = self.real();
-                    let imag = *self - real;
-                    let theta = imag.abs_sq().sqrt();
-                    let unit_imag: Self;
-
-                    if theta == 0. {
-                        unit_imag = Self::zero();
-                    } else {
-                        unit_imag = imag / theta;
-                    }
-
-                    real.exp() * (theta.cos() + unit_imag * theta.sin())
-                }
-
-                fn ln(&self) -> Self {
-                    let r = self.abs_sq().sqrt();
-                    let normed = *self / r;
-                    let real = normed.real();
-                    let imag = normed - real;
-                    let imag_mag = imag.abs_sq().sqrt();
-                    let theta = real.acos();
-                    // let sin_theta = theta.sin();
-                    let imag_exp: Self;
-
-                    if imag_mag == 0. {
-                        imag_exp = Self::zero();
-                    } else {
-                        imag_exp = theta * imag / imag_mag;
-                    }
-
-                    r.ln() + imag_exp
-                }
-
-                fn powf(&self, num: $u) -> Self {
-                    let ln_z = self.ln();
-
-                    (num * ln_z).exp()
-                }
-
-                fn powz(&self, num: Self) -> Self {
-                    let ln_z = self.ln();
-
-                    (num * ln_z).exp()
-                }
-
-                fn powu_tail(&self, num: u32, acc: Self) -> Self {
-                    if num == 0 {
-                        return acc;
-                    } else if (num % 2) == 0 {
-                        let sq = self * self;
-                        return <Self as Functions<$u, Complex<T>>>::powu_tail(
-                            &sq, num / 2, acc
-                        );
-                    } else {
-                        let sq = self * self;
-                        return <Self as Functions<$u, Complex<T>>>::powu_tail(
-                            &sq, (num - 1) / 2, self * acc
-                        );
-                    }
-                }
-
-                fn powu(&self, num: u32) -> Self {
-                    <Self as Functions<$u, Complex<T>>>::powu_tail(self, num, Self::one())
-                }
-
-                fn powi(&self, num: i32) -> Self {
-                    if num == 0 {
-                        Self::zero()
-                    } else if num < 0 {
-                        let z = self.powu(-num as u32);
-                        1. / z
-                    } else {
-                        self.powu(num as u32)
-                    }
-                }
-
-                fn sinh(&self) -> Self {
-                    let exp = self.exp();
-
-                    (exp - 1. / exp) * 0.5
-                }
-
-                fn cosh(&self) -> Self {
-                    let exp = self.exp();
-
-                    (exp + 1. / exp) * 0.5
-                }
-
-                fn tanh(&self) -> Self {
-                    let exp = self.exp();
-
-                    (exp * exp - 1.) / (exp * exp + 1.)
-                }
-
-                fn sin(&self) -> Self {
-                    let i = <Self as ImaginaryConstants>::i();
-                    let expiz = (i * *self).exp();
-
-                    (expiz - 1. / expiz) *  0.5 * -i
-                }
-
-                fn cos(&self) -> Self {
-                    let i = <Self as ImaginaryConstants>::i();
-                    let expiz = (i * *self).exp();
-
-                    (expiz + 1. / expiz) * 0.5
-                }
-
-                fn tan(&self) -> Self {
-                    let i = <Self as ImaginaryConstants>::i();
-                    let expiz = (i * *self).exp();
-
-                    (expiz * expiz - 1.) / (i * expiz * expiz + i)
-                }
-            }
-        )*
+    fn ln_on(&self, branch: Branch<S>) -> Self {
+        let (w, axis) = self.split_axis();
+
+        Self::join_axis(w.branch_ln(branch), axis)
+    }
+
+    fn powf_on(&self, num: S, branch: Branch<S>) -> Self {
+        (self.ln_on(branch) * num).exp()
     }
-}
 
-impl_functions_for_float!(f32, f64);
+    fn powz_on(&self, num: Self, branch: Branch<S>) -> Self {
+        (num * self.ln_on(branch)).exp()
+    }
+
+    fn nth_root_on(&self, n: u32, branch: Branch<S>) -> Self {
+        assert_ne!(n, 0, "the zeroth root is undefined");
+        if let Branch::Principal = branch {
+            return <Self as Functions<S, Complex<T>>>::nth_root(self, n);
+        }
+        let (w, axis) = self.split_axis();
+        let n = S::from_f64(n as f64);
+        let r = w.re.hypot(w.im);
+        let theta = branch.angle(w.im.atan2(w.re)) / n;
+
+        Self::from_polar(r.powf(S::one() / n), theta, axis)
+    }
+}
 
 /// Elementwise rounding and truncation functions
 pub trait Rounding {
@@ -562,36 +1161,27 @@ pub trait Rounding {
     fn fract(&self) -> Self;
 }
 
-macro_rules! impl_rounding_for_float {
-    ($($u:ty),* ) => {
-        $(
-            impl Rounding for $u
-            {
-                fn floor(&self) -> Self {
-                    Self::floor(*self)
-                }
-
-                fn ceil(&self) -> Self {
-                    Self::ceil(*self)
-                }
-
-                fn round(&self) -> Self {
-                    Self::round(*self)
-                }
-
-                fn trunc(&self) -> Self {
-                    Self::trunc(*self)
-                }
-
-                fn fract(&self) -> Self {
-                    Self::fract(*self)
-                }
-            }
-        )*
+impl<S: Scalar> Rounding for S {
+    fn floor(&self) -> Self {
+        Scalar::floor(*self)
+    }
+
+    fn ceil(&self) -> Self {
+        Scalar::ceil(*self)
+    }
+
+    fn round(&self) -> Self {
+        Scalar::round(*self)
+    }
+
+    fn trunc(&self) -> Self {
+        Scalar::trunc(*self)
     }
-}
 
-impl_rounding_for_float!(f32, f64);
+    fn fract(&self) -> Self {
+        Scalar::fract(*self)
+    }
+}
 
 impl<T> Rounding for Complex<T>
 where
@@ -664,23 +1254,15 @@ pub trait Identity {
     fn one() -> Self;
 }
 
-macro_rules! impl_identity_for_float {
-    ( $($u:ty),* ) => {
-        $(
-            impl Identity for $u {
-                fn zero() -> Self {
-                    0.0
-                }
-
-                fn one() -> Self {
-                    1.0
-                }
-            }
-        )*
-    };
-}
+impl<S: Scalar> Identity for S {
+    fn zero() -> Self {
+        S::from_f64(0.0)
+    }
 
-impl_identity_for_float!(f32, f64);
+    fn one() -> Self {
+        S::from_f64(1.0)
+    }
+}
 
 impl<T> Identity for Complex<T>
 where
@@ -701,6 +1283,77 @@ where
     }
 }
 
+mod sealed {
+    pub trait Sealed {}
+
+    impl<S: crate::Scalar> Sealed for S {}
+    impl<T: Sealed> Sealed for crate::Complex<T> {}
+}
+
+/// Describes the position of a type in the Cayley-Dickson construction, so that
+/// code can dispatch on the dimension of a complex or hypercomplex type at
+/// compile time. It is implemented for every `Scalar` and every `Complex<T>`
+/// built from one, and cannot be implemented for other types.
+pub trait Hypercomplex: sealed::Sealed {
+    /// The real number type at the root of the construction.
+    type Scalar: Scalar;
+    /// Number of times the construction has been applied to the `Scalar`, 0
+    /// for real numbers, 1 for complex numbers, 2 for quaternions and so on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// assert_eq!(f64::LEVEL, 0);
+    /// assert_eq!(Complexf32::LEVEL, 1);
+    /// assert_eq!(Octonionf64::LEVEL, 3);
+    /// ```
+    const LEVEL: u32;
+    /// Number of real components, 2 to the power of `LEVEL`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// assert_eq!(f32::DIM, 1);
+    /// assert_eq!(Quaternionf64::DIM, 4);
+    /// assert_eq!(Sedenionf64::DIM, 16);
+    /// ```
+    const DIM: usize;
+}
+
+/// Views the components of a number of any level, a scalar being its only
+/// component, in the order used by `Fill::from_slice`.
+pub(crate) fn scalars<H: Hypercomplex>(z: &H) -> &[H::Scalar] {
+    // SAFETY: `Complex<T>` is `repr(C)` with both fields of type `T`, and the
+    // sealed `Hypercomplex` is only implemented for a `Scalar` and `Complex<T>`
+    // built from one, so `z` is `DIM` contiguous scalars.
+    unsafe { std::slice::from_raw_parts(z as *const H as *const H::Scalar, H::DIM) }
+}
+
+/// Views the components of a number of any level as a mutable slice.
+pub(crate) fn scalars_mut<H: Hypercomplex>(z: &mut H) -> &mut [H::Scalar] {
+    // SAFETY: see `scalars`.
+    unsafe { std::slice::from_raw_parts_mut(z as *mut H as *mut H::Scalar, H::DIM) }
+}
+
+impl<S: Scalar> Hypercomplex for S {
+    type Scalar = S;
+    const LEVEL: u32 = 0;
+    const DIM: usize = 1;
+}
+
+impl<T> Hypercomplex for Complex<T>
+where
+    T: Hypercomplex,
+{
+    type Scalar = T::Scalar;
+    const LEVEL: u32 = T::LEVEL + 1;
+    const DIM: usize = 2 * T::DIM;
+}
+
 /// Generates imaginaries i, j, k for respective hypercomplex type
 pub trait ImaginaryConstants {
     /// Generates first unitary imaginary number for any hypercomplex type.
@@ -748,84 +1401,161 @@ pub trait ImaginaryConstants {
     fn k() -> Self;
 }
 
-macro_rules! impl_img_const_for_float {
-    ( $($u:ty),* ) => {
-        $(
-            impl ImaginaryConstants for $u {
-                fn i() -> Self {
-                    0.
-                }
-
-                fn j() -> Self {
-                    0.
-                }
-
-                fn k() -> Self {
-                    0.
-                }
-            }
-        )*
-    };
+impl<S: Scalar> ImaginaryConstants for S {
+    fn i() -> Self {
+        S::zero()
+    }
+
+    fn j() -> Self {
+        S::zero()
+    }
+
+    fn k() -> Self {
+        S::zero()
+    }
 }
 
-impl_img_const_for_float!(f32, f64);
 impl<T> ImaginaryConstants for Complex<T>
 where
-    T: Identity + ImaginaryConstants,
+    T: Identity + ImaginaryConstants + Hypercomplex,
 {
     fn i() -> Self {
-        if (type_name::<T>() == "f64") || (type_name::<T>() == "f32") {
-            return Self {
+        if T::LEVEL == 0 {
+            Self {
                 re: <T as Identity>::zero(),
                 im: <T as Identity>::one(),
-            };
+            }
         } else {
-            return Self {
+            Self {
                 re: <T as ImaginaryConstants>::i(),
                 im: <T as Identity>::zero(),
-            };
+            }
         }
     }
 
     fn j() -> Self {
-        if (type_name::<T>() == "f64") || (type_name::<T>() == "f32") {
-            return Self {
-                re: <T as Identity>::zero(),
-                im: <T as Identity>::one(),
-            };
-        } else if (type_name::<T>() == type_name::<Complexf64>())
-            || (type_name::<T>() == type_name::<Complexf32>())
-        {
-            return Self {
+        if T::LEVEL <= 1 {
+            Self {
                 re: <T as Identity>::zero(),
                 im: <T as Identity>::one(),
-            };
+            }
         } else {
-            return Self {
+            Self {
                 re: <T as ImaginaryConstants>::j(),
                 im: <T as Identity>::zero(),
-            };
+            }
         }
     }
 
     fn k() -> Self {
-        if (type_name::<T>() == "f64") || (type_name::<T>() == "f32") {
-            return Self {
+        match T::LEVEL {
+            0 => Self {
                 re: <T as Identity>::zero(),
                 im: <T as Identity>::one(),
-            };
-        } else if (type_name::<T>() == type_name::<Complexf64>())
-            || (type_name::<T>() == type_name::<Complexf32>())
-        {
-            return Self {
+            },
+            1 => Self {
                 re: <T as Identity>::zero(),
                 im: <T as ImaginaryConstants>::k(),
-            };
-        } else {
-            return Self {
+            },
+            _ => Self {
                 re: <T as ImaginaryConstants>::k(),
                 im: <T as Identity>::zero(),
-            };
+            },
+        }
+    }
+}
+
+/// Generates the units e0 = 1, e1, e2, ... of the Cayley-Dickson basis for any
+/// complex or hypercomplex type, numbered in the same order as the components
+/// used by `Fill::from_slice`.
+pub trait Basis: Hypercomplex + Sized {
+    /// Generates the `n`-th basis unit, or `None` if `n` is not less than
+    /// `DIM`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let e5 = Octonionf64::basis(5).unwrap();
+    ///
+    /// assert_eq!(e5, complex![0., 0., 0., 0., 0., 1., 0., 0.]);
+    /// assert_eq!(Octonionf64::basis(8), None);
+    /// ```
+    fn basis(n: usize) -> Option<Self>;
+    /// Generates the `n`-th basis unit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is not less than `DIM`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let e1 = Sedenionf64::e(1);
+    /// let e2 = Sedenionf64::e(2);
+    ///
+    /// assert_eq!(e1 * e2, Sedenionf64::e(3));
+    /// assert_eq!(Quaternionf64::e(2), Quaternionf64::j());
+    /// ```
+    fn e(n: usize) -> Self {
+        match Self::basis(n) {
+            Some(unit) => unit,
+            None => panic!(
+                "basis index {} is out of range for dimension {}",
+                n,
+                Self::DIM
+            ),
+        }
+    }
+    /// Iterates over all `DIM` basis units, starting with one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![1., 2., 3., 4.];
+    /// let sum: Quaternionf64 = Quaternionf64::basis_units()
+    ///     .zip([1., 2., 3., 4.])
+    ///     .map(|(unit, x)| unit * x)
+    ///     .sum();
+    ///
+    /// assert_eq!(Quaternionf64::basis_units().len(), 4);
+    /// assert_eq!(sum, q);
+    /// ```
+    fn basis_units() -> Map<Range<usize>, fn(usize) -> Self> {
+        (0..Self::DIM).map(Self::e)
+    }
+}
+
+impl<S: Scalar> Basis for S {
+    fn basis(n: usize) -> Option<Self> {
+        if n == 0 {
+            Some(S::one())
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Basis for Complex<T>
+where
+    T: Basis + Identity,
+{
+    fn basis(n: usize) -> Option<Self> {
+        if n < T::DIM {
+            Some(Self {
+                re: T::basis(n)?,
+                im: <T as Identity>::zero(),
+            })
+        } else {
+            Some(Self {
+                re: <T as Identity>::zero(),
+                im: T::basis(n - T::DIM)?,
+            })
         }
     }
 }
@@ -860,6 +1590,11 @@ pub trait Fill<U>: Identity {
     ///
     /// assert_eq!(filled, complex![3.0, 4.0, 5.0, 6.0]);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is empty. Use `Complex::try_from_slice` to check
+    /// that the slice has exactly as many elements as components.
     fn from_slice(v: &[U]) -> Self;
     /// Creates a hypercomplex number with all components from a vector.
     ///
@@ -875,30 +1610,27 @@ pub trait Fill<U>: Identity {
     ///
     /// assert_eq!(filled, complex![3.0, 4.0, 5.0, 6.0]);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is empty. Use `Complex::try_from` to check that
+    /// the vector has exactly as many elements as components.
     fn from_vec(v: Vec<U>) -> Self;
 }
 
-macro_rules! impl_fill_for_float {
-    ( $($u:ty),* ) => {
-        $(
-            impl Fill<$u> for $u {
-                fn fill(num: $u) -> Self {
-                    num
-                }
-
-                fn from_slice(v: &[$u]) -> Self {
-                    v[0]
-                }
-
-                fn from_vec(v: Vec<$u>) -> Self {
-                    v[0]
-                }
-            }
-        )*
-    };
-}
+impl<S: Scalar> Fill<S> for S {
+    fn fill(num: S) -> Self {
+        num
+    }
+
+    fn from_slice(v: &[S]) -> Self {
+        v[0]
+    }
 
-impl_fill_for_float!(f32, f64);
+    fn from_vec(v: Vec<S>) -> Self {
+        v[0]
+    }
+}
 
 impl<T, U> Fill<U> for Complex<T>
 where
@@ -933,6 +1665,53 @@ where
     }
 }
 
+/// Error returned when the number of components given to a fallible
+/// constructor such as `Complex::try_from_slice` does not match the dimension
+/// of the type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DimensionError {
+    /// Number of components of the type being created.
+    pub expected: usize,
+    /// Number of components that were given.
+    pub actual: usize,
+}
+
+impl std::fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected {} components but found {}.",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for DimensionError {}
+
+impl<T> TryFrom<&[T::Scalar]> for Complex<T>
+where
+    T: Hypercomplex,
+    Complex<T>: Identity,
+{
+    type Error = DimensionError;
+
+    fn try_from(v: &[T::Scalar]) -> Result<Self, Self::Error> {
+        Self::try_from_slice(v)
+    }
+}
+
+impl<T, const N: usize> TryFrom<[T::Scalar; N]> for Complex<T>
+where
+    T: Hypercomplex,
+    Complex<T>: Identity,
+{
+    type Error = DimensionError;
+
+    fn try_from(v: [T::Scalar; N]) -> Result<Self, Self::Error> {
+        Self::try_from_slice(&v)
+    }
+}
+
 /// Conjugates any complex or hypercomplex number.
 pub trait Conjugate {
     /// Return the complex conjugate of any complex or hypercomplex number
@@ -949,20 +1728,12 @@ pub trait Conjugate {
     fn conj(&self) -> Self;
 }
 
-macro_rules! impl_conj_for {
-    ( $($u:ty),* ) => {
-        $(
-            impl Conjugate for $u {
-                fn conj(&self) -> Self {
-                    *self
-                }
-            }
-        )*
-    };
+impl<S: Scalar> Conjugate for S {
+    fn conj(&self) -> Self {
+        *self
+    }
 }
 
-impl_conj_for!(f32, f64);
-
 impl<T> Conjugate for Complex<T>
 where
     T: Conjugate + Copy + Neg<Output = T>,
@@ -992,28 +1763,144 @@ pub trait AbsSq<U> {
     fn abs_sq(&self) -> U;
 }
 
-macro_rules! impl_abs_sq_for {
-    ( $($u:ty),* ) => {
-        $(
-            impl AbsSq<$u> for $u {
-                fn abs_sq(&self) -> $u {
-                    self * self
-                }
-            }
+impl<S: Scalar> AbsSq<S> for S {
+    fn abs_sq(&self) -> S {
+        *self * *self
+    }
+}
 
-            impl<T> AbsSq<$u> for Complex<T>
-            where
-                T: AbsSq<$u> + Copy + Add<Output = T>,
-            {
-                fn abs_sq(&self) -> $u {
-                    self.re.abs_sq() + self.im.abs_sq()
-                }
-            }
-        )*
-    };
+impl<S, T> AbsSq<S> for Complex<T>
+where
+    S: Scalar,
+    T: AbsSq<S>,
+{
+    fn abs_sq(&self) -> S {
+        self.re.abs_sq() + self.im.abs_sq()
+    }
+}
+
+/// Converts complex and hypercomplex numbers between Cartesian and polar form.
+/// Every hypercomplex number can be written as `r (cos θ + u sin θ)` where
+/// `r` is its modulus, `θ` its angle to the real axis and `u` the unit
+/// imaginary along its imaginary part.
+pub trait Polar<U>: Sized {
+    /// Returns the modulus of any complex or hypercomplex number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![1.0, 2.0, -2.0, 4.0];
+    ///
+    /// assert_eq!(z.abs(), 5.0);
+    /// ```
+    fn abs(&self) -> U;
+    /// Returns the angle between a complex or hypercomplex number and the
+    /// positive real axis, which lies in [0, π]. For complex numbers with a
+    /// negative imaginary part this is the magnitude of the usual argument,
+    /// the sign being carried by `unit_imaginary`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::{FRAC_PI_4, PI};
+    ///
+    /// let z: Quaternionf64 = complex![1.0, 0.0, 1.0, 0.0];
+    ///
+    /// assert!((z.arg() - FRAC_PI_4).abs() < 1e-10);
+    /// assert_eq!(complex![-2.0, 0.0].arg(), PI);
+    /// ```
+    fn arg(&self) -> U;
+    /// Returns the imaginary part of a complex or hypercomplex number scaled to
+    /// unit modulus. Real numbers have no imaginary direction and give zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let z = complex![7.0, 3.0, 0.0, 4.0];
+    ///
+    /// assert_eq!(z.unit_imaginary(), complex![0.0, 0.6, 0.0, 0.8]);
+    /// assert_eq!(complex![7.0, 0.0].unit_imaginary(), Complexf64::zero());
+    /// ```
+    fn unit_imaginary(&self) -> Self;
+    /// Returns the modulus, the angle to the real axis and the unit imaginary
+    /// of a complex or hypercomplex number, the inverse of `from_polar`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_3;
+    ///
+    /// let z = complex![1.0, 0.0, 0.0, -3.0_f64.sqrt()];
+    /// let (r, theta, axis) = z.to_polar();
+    ///
+    /// assert!((r - 2.0).abs() < 1e-10);
+    /// assert!((theta - FRAC_PI_3).abs() < 1e-10);
+    /// assert_eq!(axis, -Quaternionf64::k());
+    /// ```
+    fn to_polar(&self) -> (U, U, Self);
+    /// Creates a complex or hypercomplex number `r (cos θ + axis sin θ)` from
+    /// its modulus, its angle to the real axis and a unit imaginary `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let z = Quaternionf64::from_polar(2.0, FRAC_PI_2, Quaternionf64::j());
+    ///
+    /// assert!((z - complex![0.0, 0.0, 2.0, 0.0]).abs_sq() < 1e-10);
+    /// ```
+    fn from_polar(r: U, theta: U, axis: Self) -> Self;
 }
 
-impl_abs_sq_for!(f32, f64);
+impl<S, T> Polar<S> for Complex<T>
+where
+    S: Scalar,
+    Complex<T>: Real<S>
+        + AbsSq<S>
+        + Identity
+        + Copy
+        + Add<S, Output = Complex<T>>
+        + Sub<S, Output = Complex<T>>
+        + Mul<S, Output = Complex<T>>
+        + Div<S, Output = Complex<T>>,
+{
+    fn abs(&self) -> S {
+        self.abs_sq().sqrt()
+    }
+
+    fn arg(&self) -> S {
+        let real = self.real();
+
+        (*self - real).abs().atan2(real)
+    }
+
+    fn unit_imaginary(&self) -> Self {
+        let imag = *self - self.real();
+        let imag_mag = imag.abs();
+
+        if imag_mag == S::zero() {
+            Self::zero()
+        } else {
+            imag / imag_mag
+        }
+    }
+
+    fn to_polar(&self) -> (S, S, Self) {
+        (self.abs(), self.arg(), self.unit_imaginary())
+    }
+
+    fn from_polar(r: S, theta: S, axis: Self) -> Self {
+        (axis * theta.sin() + theta.cos()) * r
+    }
+}
 
 /// Returns the real part of any complex and hypercomplex type.
 pub trait Real<U> {
@@ -1031,25 +1918,18 @@ pub trait Real<U> {
     fn real(&self) -> U;
 }
 
-macro_rules! impl_real_for {
-    ( $($u:ty),* ) => {
-        $(
-            impl Real<$u> for $u {
-                fn real(&self) -> $u {
-                    *self
-                }
-            }
-
-            impl<T> Real<$u> for Complex<T>
-            where
-                T: Real<$u> + Copy,
-            {
-                fn real(&self) -> $u {
-                    self.re.real()
-                }
-            }
-        )*
-    };
+impl<S: Scalar> Real<S> for S {
+    fn real(&self) -> S {
+        *self
+    }
 }
 
-impl_real_for!(f32, f64);
+impl<S, T> Real<S> for Complex<T>
+where
+    S: Scalar,
+    T: Real<S>,
+{
+    fn real(&self) -> S {
+        self.re.real()
+    }
+}
@@ -11,7 +11,7 @@ fn main() {
     println!("{}", z - z2 * z3.trunc());
     println!("ratio: {}", z4);
     let z_star = z.conj();
-    let v = vec![z; 3];
+    let v = [z; 3];
     let vec = vec![
         1., 2., 3., 4., 5., 6., 7., 8., 1., 2., 3., 4., 5., 6., 7., 8.,
     ];
@@ -0,0 +1,328 @@
+//! Reading and writing arrays of complex and hypercomplex numbers in the NumPy
+//! `.npy` format, with the header parsed here rather than by NumPy.
+//!
+//! Every function is generic over the number type `H`, a scalar or any
+//! `Complex<T>`. Scalars and complex numbers are written as one dimensional
+//! arrays of `float32`, `float64`, `complex64` or `complex128`, and
+//! quaternions and above as `(N, DIM)` arrays of `float32` or `float64`
+//! holding the components in Cayley-Dickson order, so that `numpy.load`
+//! returns arrays of the expected shape. Reading accepts both byte orders,
+//! Fortran order, and `(N, DIM)` float arrays for every type.
+//!
+//! # Example
+//!
+//! ```
+//! use complex::*;
+//! use complex::npy;
+//!
+//! let values = vec![complex![1., 2., 3., 4.], complex![0., -1., 0.5, 0.]];
+//! let mut file = Vec::new();
+//!
+//! npy::write(&mut file, &values).unwrap();
+//! assert!(file.starts_with(b"\x93NUMPY\x01\x00"));
+//! assert_eq!(npy::read::<_, Quaternionf64>(&mut &file[..]).unwrap(), values);
+//! assert!(npy::read::<_, Octonionf64>(&mut &file[..]).is_err());
+//! ```
+use crate::bytes::{Endian, ScalarBytes};
+use crate::{scalars, scalars_mut, *};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+fn invalid<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Writes `values` as a version 1.0 `.npy` array.
+pub fn write<W, H>(writer: &mut W, values: &[H]) -> io::Result<()>
+where
+    W: Write,
+    H: Hypercomplex,
+    H::Scalar: ScalarBytes,
+{
+    let size = <H::Scalar as ScalarBytes>::SIZE;
+    let kind = <H::Scalar as ScalarBytes>::KIND as char;
+    let (descr, shape) = match H::LEVEL {
+        0 => (format!("<{}{}", kind, size), format!("({},)", values.len())),
+        1 => (format!("<c{}", 2 * size), format!("({},)", values.len())),
+        _ => (
+            format!("<{}{}", kind, size),
+            format!("({}, {})", values.len(), H::DIM),
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        descr, shape
+    );
+    // the data starts at a multiple of 64 bytes after the padding and newline
+    let unpadded = MAGIC.len() + 4 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    let mut bytes = vec![0; H::DIM * size];
+    for z in values {
+        for (x, chunk) in scalars(z).iter().zip(bytes.chunks_exact_mut(size)) {
+            x.write_le(chunk);
+        }
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+/// Reads a `.npy` array of numbers of type `H`. Fails with
+/// `io::ErrorKind::InvalidData` if the file is not an array of the same
+/// scalar type and dimension.
+pub fn read<R, H>(reader: &mut R) -> io::Result<Vec<H>>
+where
+    R: Read,
+    H: Hypercomplex + Identity,
+    H::Scalar: ScalarBytes,
+{
+    let mut preamble = [0; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(invalid("not a .npy file"));
+    }
+    let len = match preamble[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => return Err(invalid(format!("unsupported .npy version {}", version))),
+    };
+    let header = read_exactly(reader, len)?;
+    let header = std::str::from_utf8(&header).map_err(invalid)?;
+    let header = Header::parse(header)?;
+
+    let size = <H::Scalar as ScalarBytes>::SIZE;
+    let kind = <H::Scalar as ScalarBytes>::KIND;
+    let dim = H::DIM;
+    let endian = match header.descr.as_bytes().first() {
+        Some(b'<') | Some(b'|') => Endian::Little,
+        Some(b'>') => Endian::Big,
+        Some(b'=') if cfg!(target_endian = "little") => Endian::Little,
+        Some(b'=') => Endian::Big,
+        _ => return Err(invalid(format!("unsupported dtype '{}'", header.descr))),
+    };
+    let dtype = &header.descr[1..];
+    let count = match header.shape[..] {
+        [n] if H::LEVEL == 0 && dtype == format!("{}{}", kind as char, size) => n,
+        [n] if H::LEVEL == 1 && dtype == format!("c{}", 2 * size) => n,
+        [_, columns] if dtype == format!("{}{}", kind as char, size) && columns != dim => {
+            return Err(invalid(DimensionError {
+                expected: dim,
+                actual: columns,
+            }))
+        }
+        [n, _] if dtype == format!("{}{}", kind as char, size) => n,
+        _ => {
+            return Err(invalid(format!(
+                "cannot read an array of '{}' with shape {:?} as numbers of dimension {} of {}{}",
+                header.descr, header.shape, dim, kind as char, size
+            )))
+        }
+    };
+
+    let len = count
+        .checked_mul(dim * size)
+        .ok_or_else(|| invalid("array too large"))?;
+    let data = read_exactly(reader, len)?;
+
+    let fortran = header.fortran_order && header.shape.len() == 2;
+    let values = (0..count)
+        .map(|n| {
+            let mut z = H::zero();
+            for (m, x) in scalars_mut(&mut z).iter_mut().enumerate() {
+                let offset = if fortran { m * count + n } else { n * dim + m } * size;
+                let bytes = &data[offset..offset + size];
+                *x = match endian {
+                    Endian::Little => ScalarBytes::read_le(bytes),
+                    Endian::Big => ScalarBytes::read_be(bytes),
+                };
+            }
+            z
+        })
+        .collect();
+    Ok(values)
+}
+
+/// Reads exactly `len` bytes, growing the buffer as they arrive rather than
+/// allocating the length given by the header up front.
+fn read_exactly<R: Read>(reader: &mut R, len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() < len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the .npy file ends before its data",
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Writes `values` to a `.npy` file at `path`, replacing it if it exists.
+pub fn save<P, H>(path: P, values: &[H]) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    H: Hypercomplex,
+    H::Scalar: ScalarBytes,
+{
+    let mut writer = BufWriter::new(File::create(path)?);
+    write(&mut writer, values)?;
+    writer.flush()
+}
+
+/// Reads the `.npy` file at `path`.
+pub fn load<P, H>(path: P) -> io::Result<Vec<H>>
+where
+    P: AsRef<Path>,
+    H: Hypercomplex + Identity,
+    H::Scalar: ScalarBytes,
+{
+    read(&mut BufReader::new(File::open(path)?))
+}
+
+/// The dictionary of a `.npy` header, like
+/// `{'descr': '<f8', 'fortran_order': False, 'shape': (3, 4), }`.
+struct Header {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+/// Parser over the Python literals appearing in `.npy` headers.
+struct Literal<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Literal<'a> {
+    /// Skips whitespace and returns the next character.
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.s[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.s[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> io::Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "expected `{}` at byte {} of the .npy header",
+                c, self.pos
+            )))
+        }
+    }
+
+    fn string(&mut self) -> io::Result<&'a str> {
+        let quote = match self.peek() {
+            Some(quote @ '\'') | Some(quote @ '"') => quote,
+            _ => return Err(invalid("expected a string in the .npy header")),
+        };
+        let start = self.pos + 1;
+        let len = self.s[start..]
+            .find(quote)
+            .ok_or_else(|| invalid("unterminated string in the .npy header"))?;
+        self.pos = start + len + 1;
+        Ok(&self.s[start..start + len])
+    }
+
+    fn boolean(&mut self) -> io::Result<bool> {
+        self.peek();
+        let rest = &self.s[self.pos..];
+        for (name, value) in [("True", true), ("False", false)].iter() {
+            if rest.starts_with(name) {
+                self.pos += name.len();
+                return Ok(*value);
+            }
+        }
+        Err(invalid("expected True or False in the .npy header"))
+    }
+
+    fn integer(&mut self) -> io::Result<usize> {
+        self.peek();
+        let rest = &self.s[self.pos..];
+        let len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        self.pos += len;
+        rest[..len]
+            .parse()
+            .map_err(|_| invalid("expected an integer in the .npy header"))
+    }
+
+    fn tuple(&mut self) -> io::Result<Vec<usize>> {
+        let mut items = Vec::new();
+        self.expect('(')?;
+        while !self.eat(')') {
+            items.push(self.integer()?);
+            if !self.eat(',') {
+                self.expect(')')?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+impl Header {
+    fn parse(s: &str) -> io::Result<Self> {
+        let mut literal = Literal { s, pos: 0 };
+        let (mut descr, mut fortran_order, mut shape) = (None, None, None);
+
+        literal.expect('{')?;
+        while !literal.eat('}') {
+            match literal.string()? {
+                "descr" => {
+                    literal.expect(':')?;
+                    descr = Some(literal.string()?.to_string());
+                }
+                "fortran_order" => {
+                    literal.expect(':')?;
+                    fortran_order = Some(literal.boolean()?);
+                }
+                "shape" => {
+                    literal.expect(':')?;
+                    shape = Some(literal.tuple()?);
+                }
+                key => return Err(invalid(format!("unknown key '{}' in the .npy header", key))),
+            }
+            if !literal.eat(',') {
+                literal.expect('}')?;
+                break;
+            }
+        }
+
+        match (descr, fortran_order, shape) {
+            (Some(descr), Some(fortran_order), Some(shape)) => Ok(Self {
+                descr,
+                fortran_order,
+                shape,
+            }),
+            _ => Err(invalid("missing key in the .npy header")),
+        }
+    }
+}
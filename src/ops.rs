@@ -16,38 +16,40 @@ macro_rules! forward_ref_un_op {
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
 macro_rules! forward_ref_bin_op {
-    ($imp:ident, $method:ident, $t:ty, $u:ty, $T:tt) => {
-        impl<$T> $imp<&$u> for $t
+    ($imp:ident, $method:ident, $t:ty, $u:ty, $($T:ident $(: $bound:path)?),+) => {
+        impl<$($T $(: $bound)?),+> ::core::ops::$imp<&$u> for $t
         where
-            $t: $imp<$u> + Copy,
+            $t: ::core::ops::$imp<$u> + Copy,
             $u: Copy,
         {
-            type Output = <$t as $imp<$u>>::Output;
+            type Output = <$t as ::core::ops::$imp<$u>>::Output;
             fn $method(self, other: &$u) -> Self::Output {
-                $imp::$method(self, *other)
+                ::core::ops::$imp::$method(self, *other)
             }
         }
 
-        impl<$T> $imp<$u> for &$t
+        impl<$($T $(: $bound)?),+> ::core::ops::$imp<$u> for &$t
         where
-            $t: $imp<$u> + Copy,
+            $t: ::core::ops::$imp<$u> + Copy,
             $u: Copy,
         {
-            type Output = <$t as $imp<$u>>::Output;
+            type Output = <$t as ::core::ops::$imp<$u>>::Output;
             fn $method(self, other: $u) -> Self::Output {
-                $imp::$method(*self, other)
+                ::core::ops::$imp::$method(*self, other)
             }
         }
 
-        impl<'a, 'b, $T> $imp<&'b $u> for &'a $t
+        impl<'a, 'b, $($T $(: $bound)?),+> ::core::ops::$imp<&'b $u> for &'a $t
         where
-            $t: $imp<$u> + Copy,
+            $t: ::core::ops::$imp<$u> + Copy,
             $u: Copy,
         {
-            type Output = <$t as $imp<$u>>::Output;
+            type Output = <$t as ::core::ops::$imp<$u>>::Output;
             fn $method(self, other: &'b $u) -> Self::Output {
-                $imp::$method(*self, *other)
+                ::core::ops::$imp::$method(*self, *other)
             }
         }
     };
@@ -55,8 +57,8 @@ macro_rules! forward_ref_bin_op {
 
 macro_rules! bin_op_assign {
     ($assign:ident, $method:ident, $base_trait:ident,
-     $base_method:ident, $t:ty, $u:ty, $T:tt) => {
-        impl<$T> $assign<$u> for $t
+     $base_method:ident, $t:ty, $u:ty, $($T:ident $(: $bound:path)?),+) => {
+        impl<$($T $(: $bound)?),+> $assign<$u> for $t
         where
             $t: $base_trait<$u, Output = $t> + Copy,
         {
@@ -199,19 +201,11 @@ mod inv_real {
         }
     }
 
-    macro_rules! impl_inv_real_for_float {
-        ($($ty:ty),* ) => {
-            $(
-                impl InvReal for $ty {
-                    fn inv_real(&self) -> Self {
-                        1. / self
-                    }
-                }
-            )*
+    impl<S: Scalar> InvReal for S {
+        fn inv_real(&self) -> Self {
+            S::one() / *self
         }
     }
-
-    impl_inv_real_for_float!(f32, f64);
 }
 
 forward_ref_bin_op!(Div, div, Complex<T>, Complex<T>, T);
@@ -261,7 +255,7 @@ where
     where
         I: Iterator<Item = &'a Self>,
     {
-        iter.map(|x| *x).sum::<Self>()
+        iter.copied().sum::<Self>()
     }
 }
 
@@ -285,156 +279,182 @@ where
     where
         I: Iterator<Item = &'a Self>,
     {
-        iter.map(|x| *x).product::<Self>()
+        iter.copied().product::<Self>()
+    }
+}
+
+impl<T> Index<usize> for Complex<T>
+where
+    T: Hypercomplex,
+{
+    type Output = T::Scalar;
+    fn index(&self, n: usize) -> &Self::Output {
+        &self.as_slice()[n]
+    }
+}
+
+impl<T> IndexMut<usize> for Complex<T>
+where
+    T: Hypercomplex,
+{
+    fn index_mut(&mut self, n: usize) -> &mut Self::Output {
+        &mut self.as_mut_slice()[n]
+    }
+}
+
+impl<T, S: Scalar> Add<S> for Complex<T>
+where
+    T: Add<S, Output = T>,
+{
+    type Output = Self;
+    fn add(self, other: S) -> Self::Output {
+        Self {
+            re: self.re + other,
+            im: self.im,
+        }
+    }
+}
+
+impl<T, S: Scalar> Sub<S> for Complex<T>
+where
+    T: Sub<S, Output = T>,
+{
+    type Output = Self;
+    fn sub(self, other: S) -> Self::Output {
+        Self {
+            re: self.re - other,
+            im: self.im,
+        }
+    }
+}
+
+impl<T, S: Scalar> Mul<S> for Complex<T>
+where
+    T: Mul<S, Output = T>,
+{
+    type Output = Self;
+    fn mul(self, other: S) -> Self::Output {
+        Self {
+            re: self.re * other,
+            im: self.im * other,
+        }
     }
 }
 
-macro_rules! impl_algebra_with_reals {
+impl<T, S: Scalar> Div<S> for Complex<T>
+where
+    T: Div<S, Output = T>,
+{
+    type Output = Self;
+    fn div(self, other: S) -> Self::Output {
+        Self {
+            re: self.re / other,
+            im: self.im / other,
+        }
+    }
+}
+
+impl<T, S: Scalar> Rem<S> for Complex<T>
+where
+    Complex<T>: Rounding
+        + Sub<Output = Complex<T>>
+        + Mul<S, Output = Complex<T>>
+        + Div<S, Output = Complex<T>>
+        + Copy,
+{
+    type Output = Complex<T>;
+    fn rem(self, other: S) -> Self::Output {
+        self - (self / other).trunc() * other
+    }
+}
+
+/// Implements the arithmetic operators with a real number on the left and a
+/// complex or hypercomplex number on the right, like `2.0 * z`, and the
+/// operators taking either side by reference, for a `Scalar` type. Operators
+/// with the real number on the right by value are implemented for every
+/// `Scalar`, but the orphan rules require these ones to be generated for each
+/// type separately.
+///
+/// See the documentation of `Scalar` for an example.
+#[macro_export]
+macro_rules! impl_scalar_ops {
     ( $($ty:ty),* ) => {
         $(
-            forward_ref_bin_op!(Add, add, Complex<T>, $ty, T);
-            impl<T> Add<$ty> for Complex<T>
+            $crate::forward_ref_bin_op!(Add, add, $crate::Complex<T>, $ty, T);
+            $crate::forward_ref_bin_op!(Add, add, $ty, $crate::Complex<T>, T);
+            impl<T> ::core::ops::Add<$crate::Complex<T>> for $ty
             where
-                T: Add<$ty, Output = T>,
+                T: ::core::ops::Add<$ty, Output = T>,
             {
-                type Output = Self;
-                fn add(self, other: $ty) -> Self::Output {
-                    Self {
-                        re: self.re + other,
-                        im: self.im,
-                    }
-                }
-            }
-
-            forward_ref_bin_op!(Add, add, $ty, Complex<T>, T);
-            impl<T> Add<Complex<T>> for $ty
-            where
-                T: Add<$ty, Output = T>,
-            {
-                type Output = Complex<T>;
-                fn add(self, other: Complex<T>) -> Self::Output {
-                    Complex::<T> {
+                type Output = $crate::Complex<T>;
+                fn add(self, other: $crate::Complex<T>) -> Self::Output {
+                    $crate::Complex::<T> {
                         re: other.re + self,
                         im: other.im,
                     }
                 }
             }
 
-            forward_ref_bin_op!(Sub, sub, Complex<T>, $ty, T);
-            impl<T> Sub<$ty> for Complex<T>
-            where
-                T: Sub<$ty, Output = T>,
-            {
-                type Output = Self;
-                fn sub(self, other: $ty) -> Self::Output {
-                    Self {
-                        re: self.re - other,
-                        im: self.im,
-                    }
-                }
-            }
-
-            forward_ref_bin_op!(Sub, sub, $ty, Complex<T>, T);
-            impl<T> Sub<Complex<T>> for $ty
+            $crate::forward_ref_bin_op!(Sub, sub, $crate::Complex<T>, $ty, T);
+            $crate::forward_ref_bin_op!(Sub, sub, $ty, $crate::Complex<T>, T);
+            impl<T> ::core::ops::Sub<$crate::Complex<T>> for $ty
             where
-                T: Neg<Output = T> + Add<$ty, Output = T>,
+                T: ::core::ops::Neg<Output = T> + ::core::ops::Add<$ty, Output = T>,
             {
-                type Output = Complex<T>;
-                fn sub(self, other: Complex<T>) -> Self::Output {
-                    Complex::<T> {
+                type Output = $crate::Complex<T>;
+                fn sub(self, other: $crate::Complex<T>) -> Self::Output {
+                    $crate::Complex::<T> {
                         re: -other.re + self,
                         im: -other.im,
                     }
                 }
             }
 
-
-            forward_ref_bin_op!(Mul, mul, Complex<T>, $ty, T);
-            impl<T> Mul<$ty> for Complex<T>
-            where
-                T: Mul<$ty, Output = T>,
-            {
-                type Output = Self;
-                fn mul(self, other: $ty) -> Self::Output {
-                    Self {
-                        re: self.re * other,
-                        im: self.im * other,
-                    }
-                }
-            }
-
-            forward_ref_bin_op!(Mul, mul, $ty, Complex<T>, T);
-            impl<T> Mul<Complex<T>> for $ty
+            $crate::forward_ref_bin_op!(Mul, mul, $crate::Complex<T>, $ty, T);
+            $crate::forward_ref_bin_op!(Mul, mul, $ty, $crate::Complex<T>, T);
+            impl<T> ::core::ops::Mul<$crate::Complex<T>> for $ty
             where
-                T: Mul<$ty, Output = T>,
+                T: ::core::ops::Mul<$ty, Output = T>,
             {
-                type Output = Complex<T>;
-                fn mul(self, other: Complex<T>) -> Self::Output {
-                    Complex::<T> {
+                type Output = $crate::Complex<T>;
+                fn mul(self, other: $crate::Complex<T>) -> Self::Output {
+                    $crate::Complex::<T> {
                         re: other.re * self,
                         im: other.im * self,
                     }
                 }
             }
 
-            forward_ref_bin_op!(Div, div, Complex<T>, $ty, T);
-            impl<T> Div<$ty> for Complex<T>
-            where
-                T: Div<$ty, Output = T>,
-            {
-                type Output = Self;
-                fn div(self, other: $ty) -> Self::Output {
-                    Self{
-                        re: self.re / other,
-                        im: self.im / other
-                    }
-                }
-            }
-
-            forward_ref_bin_op!(Div, div, $ty, Complex<T>, T);
-            impl<T> Div<Complex<T>> for $ty
+            $crate::forward_ref_bin_op!(Div, div, $crate::Complex<T>, $ty, T);
+            $crate::forward_ref_bin_op!(Div, div, $ty, $crate::Complex<T>, T);
+            impl<T> ::core::ops::Div<$crate::Complex<T>> for $ty
             where
-                Complex<T>: Conjugate + AbsSq<$ty> + Mul<$ty, Output = Complex<T>> + Div<$ty, Output = Complex<T>>,
+                $crate::Complex<T>: $crate::Conjugate
+                    + $crate::AbsSq<$ty>
+                    + ::core::ops::Mul<$ty, Output = $crate::Complex<T>>,
             {
-                type Output = Complex<T>;
-                fn div(self, other: Complex<T>) -> Self::Output {
-                    other.conj() * (self / other.abs_sq())
+                type Output = $crate::Complex<T>;
+                fn div(self, other: $crate::Complex<T>) -> Self::Output {
+                    $crate::Conjugate::conj(&other) * (self / $crate::AbsSq::abs_sq(&other))
                 }
             }
 
-            impl<T> Rem<Complex<T>> for $ty
+            impl<T> ::core::ops::Rem<$crate::Complex<T>> for $ty
             where
-                Complex<T>: Rounding + Mul<Output = Complex<T>>
+                $crate::Complex<T>: $crate::Rounding
+                    + ::core::ops::Mul<Output = $crate::Complex<T>>
+                    + Copy,
+                $ty: ::core::ops::Sub<$crate::Complex<T>, Output = $crate::Complex<T>>
+                    + ::core::ops::Div<$crate::Complex<T>, Output = $crate::Complex<T>>
                     + Copy,
-                $ty: Sub<Complex<T>, Output = Complex<T>> +
-                    Div<Complex<T>, Output = Complex<T>> + Copy
-            {
-                type Output = Complex<T>;
-                fn rem(self, other: Complex<T>) -> Self::Output {
-                    self - other * (self / other).trunc()
-                }
-            }
-
-            impl<T> Rem<$ty> for Complex<T>
-            where
-                Complex<T>: Rounding
-                    + Sub<Output = Complex<T>> +
-                    Div<$ty, Output = Complex<T>> + Copy,
-                $ty: Mul<Complex<T>, Output = Complex<T>> + Copy
             {
-                type Output = Complex<T>;
-                fn rem(self, other: $ty) -> Self::Output {
-                    self - other * (self / other).trunc()
+                type Output = $crate::Complex<T>;
+                fn rem(self, other: $crate::Complex<T>) -> Self::Output {
+                    self - other * $crate::Rounding::trunc(&(self / other))
                 }
             }
-
-            bin_op_assign!(AddAssign, add_assign, Add, add, Complex<T>, $ty, T);
-            bin_op_assign!(SubAssign, sub_assign, Sub, sub, Complex<T>, $ty, T);
-            bin_op_assign!(MulAssign, mul_assign, Mul, mul, Complex<T>, $ty, T);
-            bin_op_assign!(DivAssign, div_assign, Div, div, Complex<T>, $ty, T);
-            bin_op_assign!(RemAssign, rem_assign, Rem, rem, Complex<T>, $ty, T);
         )*
-    }
+    };
 }
 
 bin_op_assign!(AddAssign, add_assign, Add, add, Complex<T>, Complex<T>, T);
@@ -442,4 +462,147 @@ bin_op_assign!(SubAssign, sub_assign, Sub, sub, Complex<T>, Complex<T>, T);
 bin_op_assign!(MulAssign, mul_assign, Mul, mul, Complex<T>, Complex<T>, T);
 bin_op_assign!(DivAssign, div_assign, Div, div, Complex<T>, Complex<T>, T);
 bin_op_assign!(RemAssign, rem_assign, Rem, rem, Complex<T>, Complex<T>, T);
-impl_algebra_with_reals!(f32, f64);
+bin_op_assign!(AddAssign, add_assign, Add, add, Complex<T>, S, T, S: Scalar);
+bin_op_assign!(SubAssign, sub_assign, Sub, sub, Complex<T>, S, T, S: Scalar);
+bin_op_assign!(MulAssign, mul_assign, Mul, mul, Complex<T>, S, T, S: Scalar);
+bin_op_assign!(DivAssign, div_assign, Div, div, Complex<T>, S, T, S: Scalar);
+bin_op_assign!(RemAssign, rem_assign, Rem, rem, Complex<T>, S, T, S: Scalar);
+impl_scalar_ops!(f32, f64);
+
+forward_ref_un_op!(Neg, neg, DualQuaternion<T>, T);
+impl<T> Neg for DualQuaternion<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = DualQuaternion<T>;
+    fn neg(self) -> Self::Output {
+        Self {
+            real: -self.real,
+            dual: -self.dual,
+        }
+    }
+}
+
+forward_ref_bin_op!(Add, add, DualQuaternion<T>, DualQuaternion<T>, T);
+impl<T> Add for DualQuaternion<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            real: self.real + other.real,
+            dual: self.dual + other.dual,
+        }
+    }
+}
+
+forward_ref_bin_op!(Sub, sub, DualQuaternion<T>, DualQuaternion<T>, T);
+impl<T> Sub for DualQuaternion<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            real: self.real - other.real,
+            dual: self.dual - other.dual,
+        }
+    }
+}
+
+forward_ref_bin_op!(Mul, mul, DualQuaternion<T>, DualQuaternion<T>, T);
+impl<T> Mul for DualQuaternion<T>
+where
+    Complex<Complex<T>>:
+        Add<Output = Complex<Complex<T>>> + Mul<Output = Complex<Complex<T>>> + Copy,
+{
+    type Output = Self;
+    fn mul(self, other: Self) -> Self::Output {
+        // (a + εb)(c + εd) = ac + ε(ad + bc) since ε² = 0
+        Self {
+            real: self.real * other.real,
+            dual: self.real * other.dual + self.dual * other.real,
+        }
+    }
+}
+
+impl<T, S: Scalar> Mul<S> for DualQuaternion<T>
+where
+    Complex<Complex<T>>: Mul<S, Output = Complex<Complex<T>>>,
+{
+    type Output = Self;
+    fn mul(self, other: S) -> Self::Output {
+        Self {
+            real: self.real * other,
+            dual: self.dual * other,
+        }
+    }
+}
+
+impl<T, S: Scalar> Div<S> for DualQuaternion<T>
+where
+    Complex<Complex<T>>: Div<S, Output = Complex<Complex<T>>>,
+{
+    type Output = Self;
+    fn div(self, other: S) -> Self::Output {
+        Self {
+            real: self.real / other,
+            dual: self.dual / other,
+        }
+    }
+}
+
+impl<T> Product for DualQuaternion<T>
+where
+    DualQuaternion<T>: Identity + Mul<Output = DualQuaternion<T>>,
+{
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.fold(Self::one(), Mul::mul)
+    }
+}
+
+impl<'a, T: 'a> Product<&'a DualQuaternion<T>> for DualQuaternion<T>
+where
+    DualQuaternion<T>: Identity + Mul<Output = DualQuaternion<T>> + Copy,
+{
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Self>,
+    {
+        iter.copied().product::<Self>()
+    }
+}
+
+bin_op_assign!(
+    AddAssign,
+    add_assign,
+    Add,
+    add,
+    DualQuaternion<T>,
+    DualQuaternion<T>,
+    T
+);
+bin_op_assign!(
+    SubAssign,
+    sub_assign,
+    Sub,
+    sub,
+    DualQuaternion<T>,
+    DualQuaternion<T>,
+    T
+);
+bin_op_assign!(
+    MulAssign,
+    mul_assign,
+    Mul,
+    mul,
+    DualQuaternion<T>,
+    DualQuaternion<T>,
+    T
+);
+bin_op_assign!(MulAssign, mul_assign, Mul, mul, DualQuaternion<T>, S, T, S: Scalar);
+bin_op_assign!(DivAssign, div_assign, Div, div, DualQuaternion<T>, S, T, S: Scalar);
@@ -0,0 +1,211 @@
+//! Principal branches of the multivalued functions on the complex plane.
+//!
+//! Every hypercomplex number lies in a complex plane spanned by the real
+//! axis and its own unit imaginary, so the functions here are evaluated on
+//! `Complex<S>` for a `Scalar` S and mapped back onto that plane by the
+//! callers in `Functions`.
+use crate::*;
+
+pub(crate) trait PrincipalBranch: Sized {
+    type Real;
+
+    /// Principal square root with the branch cut along the negative real axis.
+    fn principal_sqrt(&self) -> Self;
+    /// Principal n-th root with the branch cut along the negative real axis.
+    fn principal_nth_root(&self, n: u32) -> Self;
+    /// All n-th roots, starting from the principal root and proceeding
+    /// counterclockwise.
+    fn nth_roots(&self, n: u32) -> Vec<Self>;
+    /// Principal logarithm with the imaginary part in (-π, π].
+    fn principal_ln(&self) -> Self;
+    /// Logarithm with the imaginary part chosen by `branch`.
+    fn branch_ln(&self, branch: Branch<Self::Real>) -> Self;
+    /// Principal arcsine with branch cuts on the real axis outside [-1, 1].
+    fn principal_asin(&self) -> Self;
+    /// Principal arccosine with branch cuts on the real axis outside [-1, 1].
+    fn principal_acos(&self) -> Self;
+    /// Principal arctangent with branch cuts on the imaginary axis outside [-i, i].
+    fn principal_atan(&self) -> Self;
+    /// Principal inverse hyperbolic sine with branch cuts on the imaginary axis
+    /// outside [-i, i].
+    fn principal_asinh(&self) -> Self;
+    /// Principal inverse hyperbolic cosine with the branch cut on the real axis
+    /// below 1.
+    fn principal_acosh(&self) -> Self;
+    /// Principal inverse hyperbolic tangent with branch cuts on the real axis
+    /// outside [-1, 1].
+    fn principal_atanh(&self) -> Self;
+}
+
+impl<S: Scalar> PrincipalBranch for Complex<S> {
+    type Real = S;
+
+    fn principal_sqrt(&self) -> Self {
+        let r = self.re.hypot(self.im);
+        let half = S::from_f64(0.5);
+
+        if r == S::zero() {
+            Self::zero()
+        } else if self.re >= S::zero() {
+            let t = ((r + self.re) * half).sqrt();
+            Self::new(t, self.im / (t + t))
+        } else {
+            let t = ((r - self.re) * half).sqrt();
+            Self::new(self.im.abs() / (t + t), t.copysign(self.im))
+        }
+    }
+
+    fn principal_nth_root(&self, n: u32) -> Self {
+        if n == 2 {
+            return self.principal_sqrt();
+        }
+        let r = self.re.hypot(self.im);
+        let n = S::from_f64(n as f64);
+
+        if r == S::zero() {
+            Self::zero()
+        } else {
+            let theta = self.im.atan2(self.re) / n;
+            Self::new(theta.cos(), theta.sin()) * r.powf(S::one() / n)
+        }
+    }
+
+    fn nth_roots(&self, n: u32) -> Vec<Self> {
+        let r = self
+            .re
+            .hypot(self.im)
+            .powf(S::one() / S::from_f64(n as f64));
+        let theta = self.im.atan2(self.re);
+
+        (0..n)
+            .map(|k| {
+                let turns = S::from_f64(2. * k as f64);
+                let phi = (theta + S::pi() * turns) / S::from_f64(n as f64);
+                Self::new(phi.cos(), phi.sin()) * r
+            })
+            .collect()
+    }
+
+    fn principal_ln(&self) -> Self {
+        Self::new(self.re.hypot(self.im).ln(), self.im.atan2(self.re))
+    }
+
+    fn branch_ln(&self, branch: Branch<S>) -> Self {
+        let theta = branch.angle(self.im.atan2(self.re));
+
+        Self::new(self.re.hypot(self.im).ln(), theta)
+    }
+
+    fn principal_asin(&self) -> Self {
+        // asin(z) = -i ln(iz + sqrt(1 - z^2))
+        let i = Self::i();
+        let root = (-(*self * *self) + S::one()).principal_sqrt();
+
+        -i * (i * *self + root).principal_ln()
+    }
+
+    fn principal_acos(&self) -> Self {
+        // acos(z) = -i ln(z + i sqrt(1 - z^2))
+        let i = Self::i();
+        let root = (-(*self * *self) + S::one()).principal_sqrt();
+
+        -i * (*self + i * root).principal_ln()
+    }
+
+    fn principal_atan(&self) -> Self {
+        // atan(z) = i/2 (ln(1 - iz) - ln(1 + iz))
+        let i = Self::i();
+        let iz = i * *self;
+        let ln_diff = (-iz + S::one()).principal_ln() - (iz + S::one()).principal_ln();
+
+        i * ln_diff * S::from_f64(0.5)
+    }
+
+    fn principal_asinh(&self) -> Self {
+        // asinh(z) = ln(z + sqrt(z^2 + 1)), using asinh(-z) = -asinh(z)
+        // to avoid cancellation in the left half-plane
+        if self.re < S::zero() {
+            return -(-*self).principal_asinh();
+        }
+        let root = (*self * *self + S::one()).principal_sqrt();
+
+        (*self + root).principal_ln()
+    }
+
+    fn principal_acosh(&self) -> Self {
+        // acosh(z) = ln(z + sqrt(z + 1) sqrt(z - 1))
+        let root = (*self + S::one()).principal_sqrt() * (*self - S::one()).principal_sqrt();
+
+        (*self + root).principal_ln()
+    }
+
+    fn principal_atanh(&self) -> Self {
+        // atanh(z) = 1/2 (ln(1 + z) - ln(1 - z))
+        let ln_diff = (*self + S::one()).principal_ln() - (-*self + S::one()).principal_ln();
+
+        ln_diff * S::from_f64(0.5)
+    }
+}
+
+impl<S: Scalar> Branch<S> {
+    /// Moves a principal angle in (-π, π] onto the selected branch.
+    pub(crate) fn angle(&self, principal: S) -> S {
+        let tau = S::pi() * S::from_f64(2.);
+
+        match *self {
+            Branch::Principal => principal,
+            Branch::Sheet(k) => principal + tau * S::from_f64(k as f64),
+            Branch::Cut(alpha) => principal - tau * ((principal - alpha) / tau).ceil(),
+        }
+    }
+}
+
+/// Maps a hypercomplex number onto the complex plane spanned by the real axis
+/// and its unit imaginary, and back again.
+pub(crate) trait ImaginaryAxis<U>: Sized {
+    /// Returns the number as a point on its complex plane together with the
+    /// unit imaginary spanning that plane. The unit imaginary is oriented so
+    /// that its component along the first imaginary `i` is not negative,
+    /// which makes the plane of a `Complex<S>` the usual complex plane. Real
+    /// numbers are placed on the plane of `i`.
+    fn split_axis(&self) -> (Complex<U>, Self);
+    /// Maps a point on the complex plane back along the unit imaginary `axis`.
+    fn join_axis(w: Complex<U>, axis: Self) -> Self;
+}
+
+impl<S, T> ImaginaryAxis<S> for Complex<T>
+where
+    S: Scalar,
+    Complex<T>: Real<S>
+        + AbsSq<S>
+        + ImaginaryConstants
+        + Copy
+        + Neg<Output = Complex<T>>
+        + Mul<Output = Complex<T>>
+        + Add<S, Output = Complex<T>>
+        + Sub<S, Output = Complex<T>>
+        + Mul<S, Output = Complex<T>>
+        + Div<S, Output = Complex<T>>,
+{
+    fn split_axis(&self) -> (Complex<S>, Self) {
+        let real = self.real();
+        let imag = *self - real;
+        let imag_mag = imag.abs_sq().sqrt();
+
+        if imag_mag == S::zero() {
+            return (Complex::new(real, S::zero()), Self::i());
+        }
+        let axis = imag / imag_mag;
+
+        // the real part of i * axis is minus the component of axis along i
+        if (Self::i() * axis).real() > S::zero() {
+            (Complex::new(real, -imag_mag), -axis)
+        } else {
+            (Complex::new(real, imag_mag), axis)
+        }
+    }
+
+    fn join_axis(w: Complex<S>, axis: Self) -> Self {
+        axis * w.im + w.re
+    }
+}
@@ -0,0 +1,693 @@
+//! Rotations of three dimensional space by unit quaternions.
+//!
+//! A unit quaternion `q = cos(θ/2) + sin(θ/2) u` rotates a vector by the
+//! angle θ about the unit axis `u` through `v ↦ q v q*`, where vectors
+//! `[x, y, z]` are identified with the pure imaginary quaternions
+//! `xi + yj + zk`. Rotations compose by multiplication, `q2 * q1` applying
+//! `q1` first, and `q` and `-q` give the same rotation.
+//!
+//! Rotation matrices act on column vectors, `R v`, and are stored by rows.
+//! Euler angles follow the conventions of `EulerSequence` and `Frame`.
+//!
+//! Interpolation between orientations runs along great arcs of the unit
+//! sphere of quaternions. `slerp` and `nlerp` take the shorter of the two
+//! arcs between `q` and `±p`, while `geodesic` and `squad` follow the given
+//! signs, so a sequence of keyframes should be passed through `align` first.
+use crate::*;
+
+/// Axis sequences of Euler angles: the six Tait-Bryan sequences about three
+/// different axes, like `ZYX` for yaw, pitch and roll, and the six proper
+/// Euler sequences repeating the first axis, like `ZXZ`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum EulerSequence {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+impl EulerSequence {
+    /// Returns the indices of the axes, `0` for x, `1` for y and `2` for z.
+    fn axes(self) -> [usize; 3] {
+        match self {
+            EulerSequence::XYZ => [0, 1, 2],
+            EulerSequence::XZY => [0, 2, 1],
+            EulerSequence::YXZ => [1, 0, 2],
+            EulerSequence::YZX => [1, 2, 0],
+            EulerSequence::ZXY => [2, 0, 1],
+            EulerSequence::ZYX => [2, 1, 0],
+            EulerSequence::XYX => [0, 1, 0],
+            EulerSequence::XZX => [0, 2, 0],
+            EulerSequence::YXY => [1, 0, 1],
+            EulerSequence::YZY => [1, 2, 1],
+            EulerSequence::ZXZ => [2, 0, 2],
+            EulerSequence::ZYZ => [2, 1, 2],
+        }
+    }
+}
+
+/// Frame the axes of Euler angles are attached to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Frame {
+    /// Each rotation is about an axis of the body as rotated by the previous
+    /// ones, so intrinsic `ZYX` angles `[a, b, c]` give `z(a) y(b) x(c)`.
+    Intrinsic,
+    /// Each rotation is about an axis fixed in space, so extrinsic `XYZ`
+    /// angles `[a, b, c]` give `z(c) y(b) x(a)`, the same rotation as
+    /// intrinsic `ZYX` angles `[c, b, a]`.
+    Extrinsic,
+}
+
+impl<S: Scalar> Complex<Complex<S>> {
+    /// Creates the pure imaginary quaternion `xi + yj + zk` of a vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// assert_eq!(Quaternionf64::from_vector([1., 2., 3.]), complex![0., 1., 2., 3.]);
+    /// ```
+    pub fn from_vector(v: [S; 3]) -> Self {
+        complex![S::zero(), v[0], v[1], v[2]]
+    }
+
+    /// Returns the imaginary components `[x, y, z]` of a quaternion as a
+    /// vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// assert_eq!(complex![4., 1., 2., 3.].vector(), [1., 2., 3.]);
+    /// ```
+    pub fn vector(&self) -> [S; 3] {
+        [self.re.im, self.im.re, self.im.im]
+    }
+
+    /// Returns the quaternion scaled to unit modulus, which represents the
+    /// same rotation. Zero has no direction and gives NaN components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![1., 1., -1., 1.];
+    ///
+    /// assert_eq!(q.normalize(), complex![0.5, 0.5, -0.5, 0.5]);
+    /// ```
+    pub fn normalize(&self) -> Self {
+        *self / self.abs()
+    }
+
+    /// Creates the unit quaternion rotating by `angle` radians about `axis`,
+    /// counterclockwise when looking against the axis. The axis does not need
+    /// to be normalized, and a zero axis gives the identity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([0., 0., 2.], FRAC_PI_2);
+    /// let half = FRAC_PI_2 / 2.;
+    ///
+    /// assert_eq!(q, complex![half.cos(), 0., 0., half.sin()]);
+    /// ```
+    pub fn from_axis_angle(axis: [S; 3], angle: S) -> Self {
+        let norm = axis[0].hypot(axis[1]).hypot(axis[2]);
+        if norm == S::zero() {
+            return Self::one();
+        }
+        let half = angle * S::from_f64(0.5);
+        let sin = half.sin() / norm;
+
+        complex![half.cos(), axis[0] * sin, axis[1] * sin, axis[2] * sin]
+    }
+
+    /// Returns the unit axis and the angle in [0, π] of the rotation, the
+    /// inverse of `from_axis_angle`. The quaternion does not need to be
+    /// normalized. The identity has the angle zero and the axis `[1, 0, 0]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_3;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([0., -1., 0.], FRAC_PI_3);
+    /// let (axis, angle) = q.to_axis_angle();
+    ///
+    /// assert_eq!(axis, [0., -1., 0.]);
+    /// assert!((angle - FRAC_PI_3).abs() < 1e-12);
+    /// // -q gives the same rotation
+    /// assert_eq!((-q).to_axis_angle(), (axis, angle));
+    /// ```
+    pub fn to_axis_angle(&self) -> ([S; 3], S) {
+        let q = if self.re.re < S::zero() {
+            -*self
+        } else {
+            *self
+        };
+        let [x, y, z] = q.vector();
+        let sin = x.hypot(y).hypot(z);
+
+        if sin == S::zero() {
+            return ([S::one(), S::zero(), S::zero()], S::zero());
+        }
+        let angle = sin.atan2(q.re.re) * S::from_f64(2.);
+
+        ([x / sin, y / sin, z / sin], angle)
+    }
+
+    /// Rotates a vector by the quaternion, computing `q v q⁻¹` so that
+    /// quaternions which are not normalized still rotate without scaling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([0., 0., 1.], FRAC_PI_2);
+    /// let [x, y, z] = q.rotate_vector([1., 0., 0.]);
+    ///
+    /// assert!(x.abs() < 1e-12 && (y - 1.).abs() < 1e-12 && z == 0.);
+    /// ```
+    pub fn rotate_vector(&self, v: [S; 3]) -> [S; 3] {
+        (*self * Self::from_vector(v) * self.conj() / self.abs_sq()).vector()
+    }
+
+    /// Returns the rotation applying `self` first and `other` after it,
+    /// which is the product `other * self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let x = Quaternionf64::from_axis_angle([1., 0., 0.], FRAC_PI_2);
+    /// let z = Quaternionf64::from_axis_angle([0., 0., 1.], FRAC_PI_2);
+    /// let [a, b, c] = x.then(&z).rotate_vector([0., 1., 0.]);
+    ///
+    /// // y goes to z about x, and z stays on the axis of z
+    /// assert!(a.abs() < 1e-12 && b.abs() < 1e-12 && (c - 1.).abs() < 1e-12);
+    /// ```
+    pub fn then(&self, other: &Self) -> Self {
+        *other * *self
+    }
+
+    /// Returns the inverse rotation, `q⁻¹`, which for unit quaternions is the
+    /// conjugate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([1., 2., 3.], 0.7);
+    /// let p = q.then(&q.inverse_rotation());
+    ///
+    /// assert!((p - Quaternionf64::one()).abs() < 1e-12);
+    /// ```
+    pub fn inverse_rotation(&self) -> Self {
+        self.conj() / self.abs_sq()
+    }
+
+    /// Creates the unit quaternion rotating the direction of `from` onto the
+    /// direction of `to` along the shortest arc. Opposite directions are
+    /// rotated by π about an axis perpendicular to `from`, and a zero vector
+    /// gives the identity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = Quaternionf64::rotation_between([2., 0., 0.], [0., 3., 3.]);
+    /// let [x, y, z] = q.rotate_vector([1., 0., 0.]);
+    /// let half = 0.5f64.sqrt();
+    ///
+    /// assert!(x.abs() < 1e-12 && (y - half).abs() < 1e-12 && (z - half).abs() < 1e-12);
+    /// ```
+    pub fn rotation_between(from: [S; 3], to: [S; 3]) -> Self {
+        let zero = S::zero();
+        let norms = (from[0].hypot(from[1]).hypot(from[2])) * (to[0].hypot(to[1]).hypot(to[2]));
+        let dot = from[0] * to[0] + from[1] * to[1] + from[2] * to[2];
+        let [x, y, z] = cross(from, to);
+        let w = norms + dot;
+
+        if norms == zero {
+            return Self::one();
+        }
+        // from and to are taken as opposite when their cross product is small
+        // enough that rounding in `w` would decide the angle
+        if dot < zero && x.hypot(y).hypot(z) <= norms * tolerance::<S>() {
+            let [x, y, z] = from.map(|a| a.abs());
+            let other = if x <= y && x <= z {
+                [S::one(), zero, zero]
+            } else if y <= z {
+                [zero, S::one(), zero]
+            } else {
+                [zero, zero, S::one()]
+            };
+            return Self::from_vector(cross(from, other)).normalize();
+        }
+        complex![w, x, y, z].normalize()
+    }
+}
+
+impl<S: Scalar> Complex<Complex<S>> {
+    /// Returns the rotation matrix of the quaternion, which does not need to
+    /// be normalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([0., 0., 1.], FRAC_PI_2);
+    /// let m = q.to_rotation_matrix();
+    ///
+    /// assert!((m[0][1] + 1.).abs() < 1e-12 && (m[1][0] - 1.).abs() < 1e-12);
+    /// assert_eq!(m[2], [0., 0., 1.]);
+    /// ```
+    pub fn to_rotation_matrix(&self) -> [[S; 3]; 3] {
+        let one = S::one();
+        let s = S::from_f64(2.) / self.abs_sq();
+        let [w, x, y, z] = [self.re.re, self.re.im, self.im.re, self.im.im];
+        let (wx, wy, wz) = (w * x * s, w * y * s, w * z * s);
+        let (xx, xy, xz) = (x * x * s, x * y * s, x * z * s);
+        let (yy, yz, zz) = (y * y * s, y * z * s, z * z * s);
+
+        [
+            [one - (yy + zz), xy - wz, xz + wy],
+            [xy + wz, one - (xx + zz), yz - wx],
+            [xz - wy, yz + wx, one - (xx + yy)],
+        ]
+    }
+
+    /// Creates the unit quaternion of a rotation matrix with Shepperd's
+    /// method, which divides by the largest of the four possible pivots to
+    /// stay accurate for every rotation. The result has a non-negative real
+    /// part.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let m = [[0., 0., 1.], [1., 0., 0.], [0., 1., 0.]];
+    /// let q = Quaternionf64::from_rotation_matrix(m);
+    ///
+    /// assert!((q - complex![0.5, 0.5, 0.5, 0.5]).abs() < 1e-12);
+    /// ```
+    pub fn from_rotation_matrix(m: [[S; 3]; 3]) -> Self {
+        let one = S::one();
+        let half = S::from_f64(0.5);
+        let trace = m[0][0] + m[1][1] + m[2][2];
+
+        let q = if trace >= m[0][0] && trace >= m[1][1] && trace >= m[2][2] {
+            let r = (one + trace).sqrt();
+            let s = half / r;
+            complex![
+                r * half,
+                (m[2][1] - m[1][2]) * s,
+                (m[0][2] - m[2][0]) * s,
+                (m[1][0] - m[0][1]) * s
+            ]
+        } else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+            let r = (one + m[0][0] - m[1][1] - m[2][2]).sqrt();
+            let s = half / r;
+            complex![
+                (m[2][1] - m[1][2]) * s,
+                r * half,
+                (m[0][1] + m[1][0]) * s,
+                (m[0][2] + m[2][0]) * s
+            ]
+        } else if m[1][1] >= m[2][2] {
+            let r = (one - m[0][0] + m[1][1] - m[2][2]).sqrt();
+            let s = half / r;
+            complex![
+                (m[0][2] - m[2][0]) * s,
+                (m[0][1] + m[1][0]) * s,
+                r * half,
+                (m[1][2] + m[2][1]) * s
+            ]
+        } else {
+            let r = (one - m[0][0] - m[1][1] + m[2][2]).sqrt();
+            let s = half / r;
+            complex![
+                (m[1][0] - m[0][1]) * s,
+                (m[0][2] + m[2][0]) * s,
+                (m[1][2] + m[2][1]) * s,
+                r * half
+            ]
+        };
+
+        let q = q.normalize();
+        if q.re.re < S::zero() {
+            -q
+        } else {
+            q
+        }
+    }
+
+    /// Creates the unit quaternion of Euler angles in radians, `angles[n]`
+    /// rotating about the n-th axis of the sequence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use complex::rotation::{EulerSequence, Frame};
+    ///
+    /// let (yaw, pitch, roll) = (0.3, -0.2, 0.1);
+    /// let q = Quaternionf64::from_euler([yaw, pitch, roll], EulerSequence::ZYX, Frame::Intrinsic);
+    /// let p = Quaternionf64::from_euler([roll, pitch, yaw], EulerSequence::XYZ, Frame::Extrinsic);
+    /// let z = Quaternionf64::from_axis_angle([0., 0., 1.], yaw);
+    /// let y = Quaternionf64::from_axis_angle([0., 1., 0.], pitch);
+    /// let x = Quaternionf64::from_axis_angle([1., 0., 0.], roll);
+    ///
+    /// assert!((q - z * y * x).abs() < 1e-12);
+    /// assert!((p - q).abs() < 1e-12);
+    /// ```
+    pub fn from_euler(angles: [S; 3], sequence: EulerSequence, frame: Frame) -> Self {
+        let elementary = |n: usize| {
+            let mut axis = [S::zero(); 3];
+            axis[sequence.axes()[n]] = S::one();
+            Self::from_axis_angle(axis, angles[n])
+        };
+        let (first, second, third) = (elementary(0), elementary(1), elementary(2));
+
+        match frame {
+            Frame::Intrinsic => first * second * third,
+            Frame::Extrinsic => third * second * first,
+        }
+    }
+
+    /// Returns the Euler angles in radians of the rotation, the inverse of
+    /// `from_euler`. The first and third angles lie in [-π, π], and the
+    /// second in [0, π] for proper Euler sequences and in [-π/2, π/2] for
+    /// Tait-Bryan ones.
+    ///
+    /// At gimbal lock, where the first and third axes line up and only their
+    /// sum or difference is defined, the third angle is set to zero and the
+    /// first carries the whole rotation about the common axis. The lock is
+    /// detected within the square root of the epsilon of the scalar type of
+    /// its exact angle, about `1.5e-8` for `f64` and `3.5e-4` for `f32`.
+    ///
+    /// The quaternion does not need to be normalized. The method is the
+    /// direct one of Bernardes and Viollet, "Quaternion to Euler angles
+    /// conversion: A direct, general and computationally efficient method",
+    /// PLoS ONE 17(11), 2022.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use complex::rotation::{EulerSequence, Frame};
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let angles = [0.3, -0.2, 0.1];
+    /// let q = Quaternionf64::from_euler(angles, EulerSequence::ZXZ, Frame::Extrinsic);
+    /// let [a, b, c] = q.to_euler(EulerSequence::ZXZ, Frame::Extrinsic);
+    ///
+    /// // the second angle of proper Euler sequences is not negative
+    /// assert!((a - 0.3 + std::f64::consts::PI).abs() < 1e-12);
+    /// assert!((b - 0.2).abs() < 1e-12);
+    /// assert!((c - 0.1 + std::f64::consts::PI).abs() < 1e-12);
+    ///
+    /// // gimbal lock of yaw, pitch and roll at a pitch of π/2
+    /// let q = Quaternionf64::from_euler([0.5, FRAC_PI_2, 0.2], EulerSequence::ZYX, Frame::Intrinsic);
+    /// let [yaw, pitch, roll] = q.to_euler(EulerSequence::ZYX, Frame::Intrinsic);
+    ///
+    /// assert!((yaw - 0.3).abs() < 1e-6 && (pitch - FRAC_PI_2).abs() < 1e-6 && roll == 0.);
+    /// ```
+    pub fn to_euler(&self, sequence: EulerSequence, frame: Frame) -> [S; 3] {
+        let zero = S::zero();
+        let two = S::from_f64(2.);
+        let pi = S::pi();
+        let extrinsic = frame == Frame::Extrinsic;
+
+        // the method is formulated for extrinsic sequences, intrinsic ones
+        // are the same rotations with the axes and angles reversed
+        let [i, j, k] = match frame {
+            Frame::Extrinsic => sequence.axes(),
+            Frame::Intrinsic => {
+                let [i, j, k] = sequence.axes();
+                [k, j, i]
+            }
+        };
+        let symmetric = i == k;
+        let k = if symmetric { 3 - i - j } else { k };
+        // +1 for even permutations of the axes and -1 for odd ones
+        let sign = if (3 + j - i) % 3 == 1 {
+            S::one()
+        } else {
+            -S::one()
+        };
+
+        let q = self.as_slice();
+        let (w, qi, qj, qk) = (q[0], q[i + 1], q[j + 1], q[k + 1]);
+        let (a, b, c, d) = if symmetric {
+            (w, qi, qj, qk * sign)
+        } else {
+            (w - qj, qi + qk * sign, qj + w, qk * sign - qi)
+        };
+
+        let (first, third) = if extrinsic { (0, 2) } else { (2, 0) };
+        let mut angles = [zero; 3];
+        angles[1] = c.hypot(d).atan2(a.hypot(b)) * two;
+
+        let epsilon = tolerance::<S>();
+        let half_sum = b.atan2(a);
+        let half_diff = d.atan2(c);
+        if angles[1].abs() <= epsilon {
+            angles[0] = half_sum * two;
+        } else if (angles[1] - pi).abs() <= epsilon {
+            angles[0] = if extrinsic {
+                -half_diff * two
+            } else {
+                half_diff * two
+            };
+        } else {
+            angles[first] = half_sum - half_diff;
+            angles[third] = half_sum + half_diff;
+        }
+        if !symmetric {
+            angles[third] = angles[third] * sign;
+            angles[1] = angles[1] - pi * S::from_f64(0.5);
+        }
+
+        angles.map(|angle| {
+            if angle < -pi {
+                angle + pi * two
+            } else if angle > pi {
+                angle - pi * two
+            } else {
+                angle
+            }
+        })
+    }
+}
+
+impl<S: Scalar> Complex<Complex<S>> {
+    /// Returns the dot product of the quaternions as vectors of four
+    /// components, the cosine of half the angle between unit rotations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = complex![1., 2., 3., 4.];
+    ///
+    /// assert_eq!(q.dot(&complex![0.5, 0., -1., 1.]), 1.5);
+    /// assert_eq!(q.dot(&q), q.abs_sq());
+    /// ```
+    pub fn dot(&self, other: &Self) -> S {
+        self.re.re * other.re.re
+            + self.re.im * other.re.im
+            + self.im.re * other.im.re
+            + self.im.im * other.im.im
+    }
+
+    /// Returns `other` or `-other`, whichever is closer to the quaternion.
+    /// Both give the same rotation, and the result is at most a quarter turn
+    /// away on the unit sphere, so interpolating to it takes the shortest arc.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([0., 0., 1.], 0.5);
+    /// let p = -Quaternionf64::from_axis_angle([0., 0., 1.], 1.);
+    ///
+    /// assert_eq!(q.align(&p), -p);
+    /// assert_eq!(q.align(&-p), -p);
+    /// ```
+    pub fn align(&self, other: &Self) -> Self {
+        if self.dot(other) < S::zero() {
+            -*other
+        } else {
+            *other
+        }
+    }
+
+    /// Interpolates linearly between unit quaternions along the shortest arc
+    /// and normalizes the result. It is cheaper than `slerp` and follows the
+    /// same path, but not at constant angular velocity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let q = Quaternionf64::one();
+    /// let p = -Quaternionf64::from_axis_angle([1., 0., 0.], FRAC_PI_2);
+    /// let (axis, angle) = q.nlerp(&p, 0.5).to_axis_angle();
+    ///
+    /// assert_eq!(axis, [1., 0., 0.]);
+    /// assert!((angle - FRAC_PI_2 / 2.).abs() < 1e-12);
+    /// ```
+    pub fn nlerp(&self, other: &Self, t: S) -> Self {
+        let other = self.align(other);
+
+        (*self * (S::one() - t) + other * t).normalize()
+    }
+
+    /// Spherical linear interpolation between unit quaternions along the
+    /// shortest arc, rotating at constant angular velocity from the quaternion
+    /// at `t = 0` to `other` at `t = 1`. Nearly equal rotations fall back to
+    /// `nlerp` to avoid dividing by a vanishing sine.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([0., 1., 0.], 0.2);
+    /// let p = Quaternionf64::from_axis_angle([0., 1., 0.], 1.2 - 2. * PI);
+    /// let (axis, angle) = q.slerp(&p, 0.25).to_axis_angle();
+    ///
+    /// assert_eq!(axis, [0., 1., 0.]);
+    /// assert!((angle - 0.45).abs() < 1e-12);
+    /// ```
+    pub fn slerp(&self, other: &Self, t: S) -> Self {
+        let other = self.align(other);
+        let cos = self.dot(&other);
+        if cos > S::from_f64(0.9995) {
+            return self.nlerp(&other, t);
+        }
+        let theta = cos.acos();
+        let sin = theta.sin();
+
+        let a = (theta * (S::one() - t)).sin() / sin;
+        let b = (theta * t).sin() / sin;
+        (*self * a + other * b).normalize()
+    }
+
+    /// Interpolates along the geodesic from the quaternion to `other` as
+    /// `q (q⁻¹ p)^t`, through `Functions::ln` and `Functions::exp`. It takes
+    /// the arc given by the signs of the quaternions, which is the longer one
+    /// when their dot product is negative, and `t` may lie outside [0, 1] to
+    /// extrapolate. Magnitudes are interpolated geometrically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let q = Quaternionf64::from_axis_angle([1., 2., 2.], 0.3);
+    /// let p = Quaternionf64::from_axis_angle([-1., 0., 4.], 2.);
+    ///
+    /// assert!((q.geodesic(&p, 0.4) - q.slerp(&p, 0.4)).abs() < 1e-12);
+    /// assert!((q.geodesic(&p, 1.) - p).abs() < 1e-12);
+    /// ```
+    pub fn geodesic(&self, other: &Self, t: S) -> Self {
+        *self * (self.inverse_rotation() * *other).powf(t)
+    }
+
+    /// Spherical cubic interpolation between the quaternion at `t = 0` and
+    /// `other` at `t = 1`, with the inner control points `a` and `b`, usually
+    /// given by `squad_control`. Curves of consecutive keyframes join with a
+    /// continuous angular velocity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// let keys: Vec<_> = [0., 0.5, 1.5, 2.]
+    ///     .iter()
+    ///     .map(|&angle| Quaternionf64::from_axis_angle([0., 0., 1.], angle))
+    ///     .collect();
+    /// let a = Quaternionf64::squad_control(&keys[0], &keys[1], &keys[2]);
+    /// let b = Quaternionf64::squad_control(&keys[1], &keys[2], &keys[3]);
+    ///
+    /// assert!((keys[1].squad(&a, &b, &keys[2], 0.) - keys[1]).abs() < 1e-12);
+    /// assert!((keys[1].squad(&a, &b, &keys[2], 1.) - keys[2]).abs() < 1e-12);
+    /// ```
+    pub fn squad(&self, a: &Self, b: &Self, other: &Self, t: S) -> Self {
+        let outer = self.geodesic(other, t);
+        let inner = a.geodesic(b, t);
+
+        outer.geodesic(&inner, S::from_f64(2.) * t * (S::one() - t))
+    }
+
+    /// Returns the inner control point of `squad` at the keyframe `current`,
+    /// from its neighbours `previous` and `next`, as
+    /// `q exp(-(ln(q⁻¹ p) + ln(q⁻¹ n)) / 4)`. The neighbours are aligned to
+    /// the shortest arc from `current`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use complex::*;
+    ///
+    /// // keyframes at a constant angular velocity need no correction
+    /// let q = |angle| Quaternionf64::from_axis_angle([1., 0., 0.], angle);
+    /// let s = Quaternionf64::squad_control(&q(0.), &q(0.5), &q(1.));
+    ///
+    /// assert!((s - q(0.5)).abs() < 1e-12);
+    /// ```
+    pub fn squad_control(previous: &Self, current: &Self, next: &Self) -> Self {
+        let inverse = current.inverse_rotation();
+        let to_previous = (inverse * current.align(previous)).ln();
+        let to_next = (inverse * current.align(next)).ln();
+
+        *current * ((to_previous + to_next) * S::from_f64(-0.25)).exp()
+    }
+}
+
+pub(crate) fn cross<S: Scalar>(a: [S; 3], b: [S; 3]) -> [S; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Relative tolerance below which a quantity computed by cancellation is
+/// dominated by rounding, the square root of the epsilon of the scalar.
+fn tolerance<S: Scalar>() -> S {
+    S::epsilon().sqrt()
+}
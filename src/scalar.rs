@@ -0,0 +1,302 @@
+//! The real number types that complex and hypercomplex types are built from.
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Real numbers at the root of the `Complex<T>` tower. Every trait of this
+/// crate is implemented for types implementing `Scalar` and, recursively, for
+/// `Complex<T>` built from them, so a new real type such as a fixed-point or
+/// arbitrary-precision float only needs to implement this trait.
+///
+/// Operators with the scalar on the left, like `2.0 * z`, cannot be
+/// implemented generically; invoke `impl_scalar_ops!` for the new type to
+/// generate them.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+///
+/// #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// struct Real(f64);
+///
+/// macro_rules! forward {
+///     ($imp:ident, $method:ident) => {
+///         impl $imp for Real {
+///             type Output = Real;
+///             fn $method(self, other: Real) -> Real {
+///                 Real(self.0.$method(other.0))
+///             }
+///         }
+///     };
+/// }
+///
+/// forward!(Add, add);
+/// forward!(Sub, sub);
+/// forward!(Mul, mul);
+/// forward!(Div, div);
+/// forward!(Rem, rem);
+///
+/// impl Neg for Real {
+///     type Output = Real;
+///     fn neg(self) -> Real {
+///         Real(-self.0)
+///     }
+/// }
+///
+/// impl Scalar for Real {
+///     fn from_f64(num: f64) -> Self { Real(num) }
+///     fn pi() -> Self { Real(std::f64::consts::PI) }
+///     fn is_finite(self) -> bool { self.0.is_finite() }
+///     fn abs(self) -> Self { Real(self.0.abs()) }
+///     fn copysign(self, sign: Self) -> Self { Real(self.0.copysign(sign.0)) }
+///     fn floor(self) -> Self { Real(self.0.floor()) }
+///     fn ceil(self) -> Self { Real(self.0.ceil()) }
+///     fn round(self) -> Self { Real(self.0.round()) }
+///     fn trunc(self) -> Self { Real(self.0.trunc()) }
+///     fn fract(self) -> Self { Real(self.0.fract()) }
+///     fn sqrt(self) -> Self { Real(self.0.sqrt()) }
+///     fn cbrt(self) -> Self { Real(self.0.cbrt()) }
+///     fn hypot(self, other: Self) -> Self { Real(self.0.hypot(other.0)) }
+///     fn exp(self) -> Self { Real(self.0.exp()) }
+///     fn ln(self) -> Self { Real(self.0.ln()) }
+///     fn powf(self, num: Self) -> Self { Real(self.0.powf(num.0)) }
+///     fn powi(self, num: i32) -> Self { Real(self.0.powi(num)) }
+///     fn sin(self) -> Self { Real(self.0.sin()) }
+///     fn cos(self) -> Self { Real(self.0.cos()) }
+///     fn tan(self) -> Self { Real(self.0.tan()) }
+///     fn asin(self) -> Self { Real(self.0.asin()) }
+///     fn acos(self) -> Self { Real(self.0.acos()) }
+///     fn atan(self) -> Self { Real(self.0.atan()) }
+///     fn atan2(self, other: Self) -> Self { Real(self.0.atan2(other.0)) }
+///     fn sinh(self) -> Self { Real(self.0.sinh()) }
+///     fn cosh(self) -> Self { Real(self.0.cosh()) }
+///     fn tanh(self) -> Self { Real(self.0.tanh()) }
+///     fn asinh(self) -> Self { Real(self.0.asinh()) }
+///     fn acosh(self) -> Self { Real(self.0.acosh()) }
+///     fn atanh(self) -> Self { Real(self.0.atanh()) }
+/// }
+///
+/// impl_scalar_ops!(Real);
+///
+/// let q = Complex::new(
+///     Complex::new(Real(1.0), Real(2.0)),
+///     Complex::new(Real(2.0), Real(4.0)),
+/// );
+///
+/// assert_eq!(q.abs(), Real(5.0));
+/// assert_eq!(Real(2.0) * q, q + q);
+/// assert_eq!(q.conj() * q, Complex::one() * Real(25.0));
+/// ```
+pub trait Scalar:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Converts an `f64`, used for the numerical constants in the algorithms
+    /// of this crate.
+    fn from_f64(num: f64) -> Self;
+    /// Returns the constant π.
+    fn pi() -> Self;
+    /// Returns the machine epsilon, the difference between one and the next
+    /// larger number, which scales the tolerances of the algorithms of this
+    /// crate. Defaults to the epsilon of `f64`.
+    fn epsilon() -> Self {
+        Self::from_f64(f64::EPSILON)
+    }
+    /// Returns `true` if the number is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+    /// Returns the absolute value.
+    fn abs(self) -> Self;
+    /// Returns the magnitude of `self` with the sign of `sign`.
+    fn copysign(self, sign: Self) -> Self;
+    /// Returns the largest integer less than or equal to a number.
+    fn floor(self) -> Self;
+    /// Returns the smallest integer greater than or equal to a number.
+    fn ceil(self) -> Self;
+    /// Returns the nearest integer, rounding half-way cases away from zero.
+    fn round(self) -> Self;
+    /// Returns the integer part of a number.
+    fn trunc(self) -> Self;
+    /// Returns the fractional part of a number.
+    fn fract(self) -> Self;
+    /// Returns the square root.
+    fn sqrt(self) -> Self;
+    /// Returns the real cube root.
+    fn cbrt(self) -> Self;
+    /// Returns `sqrt(self * self + other * other)` without undue overflow.
+    fn hypot(self, other: Self) -> Self;
+    /// Returns `e` to the power of a number.
+    fn exp(self) -> Self;
+    /// Returns the natural logarithm.
+    fn ln(self) -> Self;
+    /// Raises a number to a real power.
+    fn powf(self, num: Self) -> Self;
+    /// Raises a number to an integer power.
+    fn powi(self, num: i32) -> Self;
+    /// Returns the sine.
+    fn sin(self) -> Self;
+    /// Returns the cosine.
+    fn cos(self) -> Self;
+    /// Returns the tangent.
+    fn tan(self) -> Self;
+    /// Returns the arcsine in [-π/2, π/2].
+    fn asin(self) -> Self;
+    /// Returns the arccosine in [0, π].
+    fn acos(self) -> Self;
+    /// Returns the arctangent in [-π/2, π/2].
+    fn atan(self) -> Self;
+    /// Returns the four quadrant arctangent of `self` (y) and `other` (x) in
+    /// [-π, π].
+    fn atan2(self, other: Self) -> Self;
+    /// Returns the hyperbolic sine.
+    fn sinh(self) -> Self;
+    /// Returns the hyperbolic cosine.
+    fn cosh(self) -> Self;
+    /// Returns the hyperbolic tangent.
+    fn tanh(self) -> Self;
+    /// Returns the inverse hyperbolic sine.
+    fn asinh(self) -> Self;
+    /// Returns the inverse hyperbolic cosine.
+    fn acosh(self) -> Self;
+    /// Returns the inverse hyperbolic tangent.
+    fn atanh(self) -> Self;
+}
+
+macro_rules! impl_scalar_for_float {
+    ( $($u:ident),* ) => {
+        $(
+            impl Scalar for $u {
+                fn from_f64(num: f64) -> Self {
+                    num as $u
+                }
+
+                fn pi() -> Self {
+                    std::$u::consts::PI
+                }
+
+                fn epsilon() -> Self {
+                    $u::EPSILON
+                }
+
+                fn is_finite(self) -> bool {
+                    $u::is_finite(self)
+                }
+
+                fn abs(self) -> Self {
+                    $u::abs(self)
+                }
+
+                fn copysign(self, sign: Self) -> Self {
+                    $u::copysign(self, sign)
+                }
+
+                fn floor(self) -> Self {
+                    $u::floor(self)
+                }
+
+                fn ceil(self) -> Self {
+                    $u::ceil(self)
+                }
+
+                fn round(self) -> Self {
+                    $u::round(self)
+                }
+
+                fn trunc(self) -> Self {
+                    $u::trunc(self)
+                }
+
+                fn fract(self) -> Self {
+                    $u::fract(self)
+                }
+
+                fn sqrt(self) -> Self {
+                    $u::sqrt(self)
+                }
+
+                fn cbrt(self) -> Self {
+                    $u::cbrt(self)
+                }
+
+                fn hypot(self, other: Self) -> Self {
+                    $u::hypot(self, other)
+                }
+
+                fn exp(self) -> Self {
+                    $u::exp(self)
+                }
+
+                fn ln(self) -> Self {
+                    $u::ln(self)
+                }
+
+                fn powf(self, num: Self) -> Self {
+                    $u::powf(self, num)
+                }
+
+                fn powi(self, num: i32) -> Self {
+                    $u::powi(self, num)
+                }
+
+                fn sin(self) -> Self {
+                    $u::sin(self)
+                }
+
+                fn cos(self) -> Self {
+                    $u::cos(self)
+                }
+
+                fn tan(self) -> Self {
+                    $u::tan(self)
+                }
+
+                fn asin(self) -> Self {
+                    $u::asin(self)
+                }
+
+                fn acos(self) -> Self {
+                    $u::acos(self)
+                }
+
+                fn atan(self) -> Self {
+                    $u::atan(self)
+                }
+
+                fn atan2(self, other: Self) -> Self {
+                    $u::atan2(self, other)
+                }
+
+                fn sinh(self) -> Self {
+                    $u::sinh(self)
+                }
+
+                fn cosh(self) -> Self {
+                    $u::cosh(self)
+                }
+
+                fn tanh(self) -> Self {
+                    $u::tanh(self)
+                }
+
+                fn asinh(self) -> Self {
+                    $u::asinh(self)
+                }
+
+                fn acosh(self) -> Self {
+                    $u::acosh(self)
+                }
+
+                fn atanh(self) -> Self {
+                    $u::atanh(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_for_float!(f32, f64);
@@ -0,0 +1,139 @@
+//! Serialization with [serde](https://serde.rs), enabled by the `serde`
+//! feature.
+//!
+//! `Complex<T>` implements `Serialize` and `Deserialize` as the nested
+//! `{re, im}` structure it is built from, so a quaternion is written as
+//! `{"re": {"re": w, "im": x}, "im": {"re": y, "im": z}}`. The modules
+//! `flat` and `nested` are helpers for `#[serde(with = "...")]` that select
+//! the flat component array `[w, x, y, z]` or the nested structure for a
+//! field.
+//!
+//! # Example
+//!
+//! ```
+//! use complex::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Pose {
+//!     #[serde(with = "complex::serialize::flat")]
+//!     rotation: Quaternionf64,
+//!     spin: Complexf64,
+//! }
+//!
+//! let pose = Pose {
+//!     rotation: complex![1., 0., 0., 0.],
+//!     spin: complex![0.5, -1.],
+//! };
+//! let json = serde_json::to_string(&pose).unwrap();
+//!
+//! assert_eq!(
+//!     json,
+//!     r#"{"rotation":[1.0,0.0,0.0,0.0],"spin":{"re":0.5,"im":-1.0}}"#
+//! );
+//! assert_eq!(serde_json::from_str::<Pose>(&json).unwrap(), pose);
+//! assert!(serde_json::from_str::<Pose>(
+//!     r#"{"rotation":[1.0,0.0,0.0],"spin":{"re":0.5,"im":-1.0}}"#
+//! )
+//! .is_err());
+//! ```
+
+/// Serializes a number as a flat array of its `DIM` components in the order
+/// of `Complex::as_slice`, like `[w, x, y, z]` for a quaternion. Deserializing
+/// fails with the message of a `DimensionError` unless there are exactly
+/// `DIM` components.
+pub mod flat {
+    use crate::*;
+    use ::serde::de::{self, Deserializer, IgnoredAny, SeqAccess, Visitor};
+    use ::serde::{Deserialize, Serialize, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    /// Serializes the components of `z` as a sequence.
+    pub fn serialize<T, S>(z: &Complex<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Hypercomplex,
+        T::Scalar: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(z.components())
+    }
+
+    /// Deserializes a sequence of exactly `DIM` components.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Complex<T>, D::Error>
+    where
+        T: Hypercomplex,
+        T::Scalar: Deserialize<'de>,
+        Complex<T>: Identity,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(FlatVisitor(PhantomData))
+    }
+
+    struct FlatVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for FlatVisitor<T>
+    where
+        T: Hypercomplex,
+        T::Scalar: Deserialize<'de>,
+        Complex<T>: Identity,
+    {
+        type Value = Complex<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a sequence of {} components", Complex::<T>::DIM)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut z = Complex::<T>::zero();
+            let expected = Complex::<T>::DIM;
+            let mut actual = 0;
+
+            while actual < expected {
+                match seq.next_element()? {
+                    Some(x) => z.as_mut_slice()[actual] = x,
+                    None => break,
+                }
+                actual += 1;
+            }
+            while seq.next_element::<IgnoredAny>()?.is_some() {
+                actual += 1;
+            }
+            if actual == expected {
+                Ok(z)
+            } else {
+                Err(de::Error::custom(DimensionError { expected, actual }))
+            }
+        }
+    }
+}
+
+/// Serializes a number as the nested `{re, im}` structure of its levels, the
+/// same as the `Serialize` and `Deserialize` implementations of `Complex<T>`.
+/// It exists to state the representation explicitly next to fields using
+/// `flat`.
+pub mod nested {
+    use crate::*;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes `z` as `{re, im}`.
+    pub fn serialize<T, S>(z: &Complex<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        z.serialize(serializer)
+    }
+
+    /// Deserializes `{re, im}`, rejecting unknown fields.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Complex<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Complex::<T>::deserialize(deserializer)
+    }
+}
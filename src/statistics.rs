@@ -0,0 +1,228 @@
+//! Averages and spread of batches of orientations, given as unit quaternions.
+//!
+//! Since `q` and `-q` give the same rotation, every function here is
+//! independent of the signs of the samples. Samples are not required to be
+//! normalized, only their direction on the unit sphere is used. Deviations
+//! from a mean are measured in its tangent space as rotation vectors, the axis
+//! of the rotation from the mean to the sample scaled by its angle in radians,
+//! expressed in the frame of the mean.
+//!
+//! # Example
+//!
+//! ```
+//! use complex::*;
+//! use complex::statistics;
+//!
+//! let samples: Vec<_> = [0.1, 0.2, 0.3]
+//!     .iter()
+//!     .map(|&angle| Quaternionf64::from_axis_angle([0., 0., 1.], angle))
+//!     .collect();
+//! let mean = statistics::karcher_mean(&samples).unwrap();
+//!
+//! assert!(statistics::angular_distance(&mean, &samples[1]) < 1e-12);
+//! ```
+use crate::*;
+
+/// Largest norm of a Karcher mean update at which the iteration stops.
+const TOLERANCE: f64 = 1e-12;
+/// Largest number of Karcher mean updates.
+const MAX_ITERATIONS: usize = 100;
+/// Largest number of sweeps of the Jacobi eigenvalue iteration.
+const MAX_SWEEPS: usize = 50;
+
+/// Returns the angle in radians, in [0, π], of the rotation taking `a` to `b`.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use complex::statistics::angular_distance;
+///
+/// let a = Quaternionf64::from_axis_angle([1., 0., 0.], 0.5);
+/// let b = Quaternionf64::from_axis_angle([1., 0., 0.], -0.25);
+///
+/// assert!((angular_distance(&a, &b) - 0.75).abs() < 1e-12);
+/// assert!((angular_distance(&a, &-b) - 0.75).abs() < 1e-12);
+/// ```
+pub fn angular_distance(a: &Quaternionf64, b: &Quaternionf64) -> f64 {
+    let d = a.conj() * *b;
+    let [x, y, z] = d.vector();
+
+    2. * x.hypot(y).hypot(z).atan2(d.re.re.abs())
+}
+
+/// Returns the mean rotation of the samples by Markley's method, the
+/// eigenvector of the largest eigenvalue of `Σ q qᵀ`, which minimizes the sum
+/// of squared chordal distances `‖R - Rᵢ‖²` of rotation matrices. The result
+/// has a non-negative real part, and is `None` for an empty slice.
+///
+/// F. L. Markley, Y. Cheng, J. L. Crassidis and Y. Oshman, "Averaging
+/// Quaternions", Journal of Guidance, Control, and Dynamics 30(4), 2007.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use complex::statistics::markley_mean;
+///
+/// let a = Quaternionf64::from_axis_angle([0., 1., 0.], 0.4);
+/// let b = -Quaternionf64::from_axis_angle([0., 1., 0.], 0.8);
+/// let mean = markley_mean(&[a, b]).unwrap();
+///
+/// assert!((mean - Quaternionf64::from_axis_angle([0., 1., 0.], 0.6)).abs() < 1e-12);
+/// assert_eq!(markley_mean(&[]), None);
+/// ```
+pub fn markley_mean(samples: &[Quaternionf64]) -> Option<Quaternionf64> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut m = [[0.; 4]; 4];
+    for q in samples {
+        let q = q.normalize();
+        let q = q.as_slice();
+        for (row, &a) in m.iter_mut().zip(q) {
+            for (entry, &b) in row.iter_mut().zip(q) {
+                *entry += a * b;
+            }
+        }
+    }
+
+    let [w, x, y, z] = principal_eigenvector(m);
+    let mean = complex![w, x, y, z].normalize();
+    Some(if w < 0. { -mean } else { mean })
+}
+
+/// Returns the Karcher mean of the samples, the rotation minimizing the sum of
+/// squared angular distances to them. Starting from `markley_mean`, it moves
+/// the mean by the average of the samples in its tangent space, through
+/// `Functions::ln` and `Functions::exp`, until the update is negligible. The
+/// result has a non-negative real part, and is `None` for an empty slice.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use complex::statistics::{angular_distance, karcher_mean};
+///
+/// let samples = [
+///     Quaternionf64::from_axis_angle([1., 0., 0.], 0.3),
+///     Quaternionf64::from_axis_angle([0., 1., 0.], 0.3),
+///     Quaternionf64::from_axis_angle([0., 0., 1.], 0.3),
+/// ];
+/// let mean = karcher_mean(&samples).unwrap();
+/// let distance = angular_distance(&mean, &samples[0]);
+///
+/// assert!(samples.iter().all(|q| (angular_distance(&mean, q) - distance).abs() < 1e-12));
+/// ```
+pub fn karcher_mean(samples: &[Quaternionf64]) -> Option<Quaternionf64> {
+    let mut mean = markley_mean(samples)?;
+    let n = samples.len() as f64;
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut step = [0.; 3];
+        for q in samples {
+            for (s, v) in step.iter_mut().zip(tangent(&mean, q)) {
+                *s += v / n;
+            }
+        }
+        mean = (mean * Quaternionf64::from_vector(step.map(|s| s / 2.)).exp()).normalize();
+        if step[0].hypot(step[1]).hypot(step[2]) < TOLERANCE {
+            break;
+        }
+    }
+
+    Some(if mean.re.re < 0. { -mean } else { mean })
+}
+
+/// Returns the 3x3 covariance of the samples about `mean`, the average of the
+/// outer products of their rotation vectors in the tangent space of the mean,
+/// in squared radians. It divides by the number of samples and does not
+/// subtract the average rotation vector, which vanishes at the Karcher mean.
+/// The result is `None` for an empty slice.
+///
+/// # Example
+///
+/// ```
+/// use complex::*;
+/// use complex::statistics::covariance;
+///
+/// let samples = [
+///     Quaternionf64::from_axis_angle([0., 0., 1.], 0.1),
+///     Quaternionf64::from_axis_angle([0., 0., 1.], -0.1),
+/// ];
+/// let c = covariance(&samples, &Quaternionf64::one()).unwrap();
+///
+/// assert!((c[2][2] - 0.01).abs() < 1e-12);
+/// assert_eq!(c[0], [0., 0., 0.]);
+/// ```
+pub fn covariance(samples: &[Quaternionf64], mean: &Quaternionf64) -> Option<[[f64; 3]; 3]> {
+    if samples.is_empty() {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mut c = [[0.; 3]; 3];
+    for q in samples {
+        let v = tangent(mean, q);
+        for (row, &a) in c.iter_mut().zip(&v) {
+            for (entry, &b) in row.iter_mut().zip(&v) {
+                *entry += a * b / n;
+            }
+        }
+    }
+
+    Some(c)
+}
+
+/// Returns the rotation vector of `q` in the tangent space of `mean`, twice
+/// the logarithm of the rotation between them along the shortest arc.
+fn tangent(mean: &Quaternionf64, q: &Quaternionf64) -> [f64; 3] {
+    let d = mean.inverse_rotation() * mean.align(q);
+
+    d.normalize().ln().vector().map(|x| 2. * x)
+}
+
+/// Returns the unit eigenvector of the largest eigenvalue of a symmetric 4x4
+/// matrix, by the cyclic Jacobi eigenvalue iteration.
+fn principal_eigenvector(mut a: [[f64; 4]; 4]) -> [f64; 4] {
+    let mut v = [[0.; 4]; 4];
+    for (n, row) in v.iter_mut().enumerate() {
+        row[n] = 1.;
+    }
+
+    for _ in 0..MAX_SWEEPS {
+        // stop once the off-diagonal entries are negligible next to the
+        // diagonal, as rounding may keep them from reaching zero
+        let off: f64 = (0..4)
+            .flat_map(|p| (p + 1..4).map(move |q| (p, q)))
+            .map(|(p, q)| a[p][q] * a[p][q])
+            .sum();
+        let diagonal: f64 = (0..4).map(|n| a[n][n] * a[n][n]).sum();
+        if off <= f64::EPSILON * f64::EPSILON * diagonal {
+            break;
+        }
+        for p in 0..3 {
+            for q in p + 1..4 {
+                if a[p][q] == 0. {
+                    continue;
+                }
+                // the rotation in the (p, q) plane zeroing a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = theta.signum() / (theta.abs() + theta.hypot(1.));
+                let c = 1. / t.hypot(1.);
+                let s = t * c;
+
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                a[p] = [0, 1, 2, 3].map(|k| c * row_p[k] - s * row_q[k]);
+                a[q] = [0, 1, 2, 3].map(|k| s * row_p[k] + c * row_q[k]);
+            }
+        }
+    }
+
+    let largest = (1..4).fold(0, |m, n| if a[n][n] > a[m][m] { n } else { m });
+    [v[0][largest], v[1][largest], v[2][largest], v[3][largest]]
+}
@@ -0,0 +1,504 @@
+//! Reading and writing tables of complex and hypercomplex numbers as CSV.
+//!
+//! Every column of a table holds numbers of the same type. A column is laid
+//! out either as one text cell in the syntax of `Display` and `FromStr`,
+//! like `1 + 2i - 3j`, or as `DIM` adjacent numeric cells named after the
+//! components, like `w,x,y,z`. Cells are quoted as in RFC 4180 when needed.
+//! `Reader` is an iterator over rows that reads one record at a time, and
+//! its errors report the row and column in the file, counting from one.
+//!
+//! # Example
+//!
+//! ```
+//! use complex::*;
+//! use complex::table::{Format, Reader, Writer};
+//!
+//! let rows = vec![
+//!     [complex![1., 0., 0., 0.], complex![0.5, -0.5, 0., 0.]],
+//!     [complex![0., 1., 0., 0.], complex![0., 0., 2., 1.]],
+//! ];
+//! let mut file = Vec::new();
+//! let mut writer = Writer::new(&mut file, &["a", "b"], Format::components()).unwrap();
+//! for row in rows.iter() {
+//!     writer.write_row(row).unwrap();
+//! }
+//!
+//! assert_eq!(
+//!     String::from_utf8(file.clone()).unwrap(),
+//!     "a_w,a_x,a_y,a_z,b_w,b_x,b_y,b_z\n\
+//!      1,0,0,0,0.5,-0.5,0,0\n\
+//!      0,1,0,0,0,0,2,1\n"
+//! );
+//!
+//! let reader = Reader::<_, Quaternionf64>::new(&file[..], &["a", "b"], Format::components());
+//! let read: Vec<Vec<Quaternionf64>> = reader.unwrap().collect::<Result<_, _>>().unwrap();
+//! assert_eq!(read, rows);
+//! ```
+use crate::fmt::ComplexParseError;
+use crate::{scalars, scalars_mut, *};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// How a column of numbers is laid out in cells.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Layout {
+    /// One cell holding the text of `Display`, parsed back with `FromStr`.
+    #[default]
+    Text,
+    /// `DIM` adjacent cells holding the components.
+    Components,
+}
+
+/// Names of the component cells in the header row.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub enum ComponentNames {
+    /// `re,im` for complex numbers, `w,x,y,z` for quaternions and `e0`, `e1`,
+    /// ... above.
+    #[default]
+    Standard,
+    /// `e0`, `e1`, ... at every level.
+    Indexed,
+    /// The given names, one per component.
+    Custom(Vec<String>),
+}
+
+impl ComponentNames {
+    fn names(&self, dim: usize) -> Vec<String> {
+        match (self, dim) {
+            (ComponentNames::Standard, 2) => vec!["re".into(), "im".into()],
+            (ComponentNames::Standard, 4) => ["w", "x", "y", "z"]
+                .iter()
+                .map(|&name| name.into())
+                .collect(),
+            (ComponentNames::Custom(names), _) => {
+                assert!(
+                    names.len() == dim,
+                    "expected {} component names but found {}",
+                    dim,
+                    names.len()
+                );
+                names.clone()
+            }
+            _ => (0..dim).map(|n| format!("e{}", n)).collect(),
+        }
+    }
+}
+
+/// Options of a CSV table, shared by `Reader` and `Writer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    layout: Layout,
+    names: ComponentNames,
+    delimiter: char,
+    header: bool,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            layout: Layout::default(),
+            names: ComponentNames::default(),
+            delimiter: ',',
+            header: true,
+        }
+    }
+}
+
+impl Format {
+    /// Columns in one text cell each, with a header row and commas.
+    pub fn text() -> Self {
+        Self::default()
+    }
+
+    /// Columns in one cell per component, with a header row and commas.
+    pub fn components() -> Self {
+        Self::default().layout(Layout::Components)
+    }
+
+    /// Selects the layout of the columns.
+    pub fn layout(self, layout: Layout) -> Self {
+        Self { layout, ..self }
+    }
+
+    /// Selects the names of component cells in the header row. A column
+    /// named `q` has the cells `q_w`, `q_x`, ..., and a column with an empty
+    /// name just `w`, `x`, ....
+    pub fn component_names(self, names: ComponentNames) -> Self {
+        Self { names, ..self }
+    }
+
+    /// Selects the character separating cells, like `;` or `\t`.
+    pub fn delimiter(self, delimiter: char) -> Self {
+        Self { delimiter, ..self }
+    }
+
+    /// Selects whether the table starts with a header row.
+    pub fn header(self, header: bool) -> Self {
+        Self { header, ..self }
+    }
+
+    /// Returns the header cells of the given columns.
+    fn header_cells(&self, columns: &[&str], dim: usize) -> Vec<String> {
+        match self.layout {
+            Layout::Text => columns.iter().map(|&column| column.into()).collect(),
+            Layout::Components => {
+                let names = self.names.names(dim);
+                columns
+                    .iter()
+                    .flat_map(|column| {
+                        names.iter().map(move |name| match *column {
+                            "" => name.clone(),
+                            column => format!("{}_{}", column, name),
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the number of cells of a row.
+    fn cells(&self, columns: usize, dim: usize) -> usize {
+        match self.layout {
+            Layout::Text => columns,
+            Layout::Components => columns * dim,
+        }
+    }
+}
+
+/// The reason reading a table failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsvErrorKind {
+    /// Reading from the underlying reader failed.
+    Io(io::Error),
+    /// A text cell is not a number of the expected type.
+    Parse(ComplexParseError),
+    /// A numeric cell is not a number.
+    Number(String),
+    /// A row has a different number of cells than the table.
+    CellCount { expected: usize, actual: usize },
+    /// A header cell differs from the expected name.
+    Header { expected: String, actual: String },
+    /// A quoted cell is not closed before the end of the input.
+    UnterminatedQuote,
+}
+
+/// Error returned when reading a table fails, with the row and column of the
+/// offending cell in the file, counting from one.
+#[derive(Debug)]
+pub struct CsvError {
+    /// Line of the file where the row starts.
+    pub row: usize,
+    /// Cell within the row.
+    pub column: usize,
+    /// The reason reading failed.
+    pub kind: CsvErrorKind,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid table at row {}, column {}: ",
+            self.row, self.column
+        )?;
+        match &self.kind {
+            CsvErrorKind::Io(err) => write!(f, "{}", err),
+            CsvErrorKind::Parse(err) => write!(f, "{}", err),
+            CsvErrorKind::Number(cell) => write!(f, "invalid number `{}`.", cell),
+            CsvErrorKind::CellCount { expected, actual } => {
+                write!(f, "expected {} cells but found {}.", expected, actual)
+            }
+            CsvErrorKind::Header { expected, actual } => {
+                write!(f, "expected header `{}` but found `{}`.", expected, actual)
+            }
+            CsvErrorKind::UnterminatedQuote => write!(f, "unterminated quote."),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            CsvErrorKind::Io(err) => Some(err),
+            CsvErrorKind::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Writes rows of numbers of type `H` as CSV.
+#[derive(Debug)]
+pub struct Writer<W, H> {
+    writer: W,
+    format: Format,
+    columns: usize,
+    marker: PhantomData<H>,
+}
+
+impl<W, H> Writer<W, H>
+where
+    W: Write,
+    H: Hypercomplex + fmt::Display,
+    H::Scalar: fmt::Display,
+{
+    /// Creates a writer of tables with the given columns, writing the header
+    /// row if the format has one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if custom component names are not one per component.
+    pub fn new(writer: W, columns: &[&str], format: Format) -> io::Result<Self> {
+        let mut writer = Self {
+            writer,
+            columns: columns.len(),
+            format,
+            marker: PhantomData,
+        };
+        if writer.format.header {
+            let cells = writer.format.header_cells(columns, H::DIM);
+            writer.write_cells(cells.iter().map(|cell| cell.as_str()))?;
+        }
+        Ok(writer)
+    }
+
+    /// Writes a row with one number per column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not have one number per column.
+    pub fn write_row(&mut self, row: &[H]) -> io::Result<()> {
+        assert!(
+            row.len() == self.columns,
+            "expected {} numbers in the row but found {}",
+            self.columns,
+            row.len()
+        );
+        let cells: Vec<String> = match self.format.layout {
+            Layout::Text => row.iter().map(|z| z.to_string()).collect(),
+            Layout::Components => row
+                .iter()
+                .flat_map(|z| scalars(z).iter().map(|x| x.to_string()))
+                .collect(),
+        };
+        self.write_cells(cells.iter().map(|cell| cell.as_str()))
+    }
+
+    fn write_cells<'a, I>(&mut self, cells: I) -> io::Result<()>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let delimiter = self.format.delimiter;
+        let mut line = String::new();
+        for (n, cell) in cells.enumerate() {
+            if n > 0 {
+                line.push(delimiter);
+            }
+            if cell.contains([delimiter, '"', '\n', '\r']) {
+                line.push('"');
+                line.push_str(&cell.replace('"', "\"\""));
+                line.push('"');
+            } else {
+                line.push_str(cell);
+            }
+        }
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads rows of numbers of type `H` from CSV, as an iterator over the rows
+/// that stops at the end of the input.
+#[derive(Debug)]
+pub struct Reader<R, H> {
+    reader: R,
+    format: Format,
+    columns: usize,
+    line: usize,
+    marker: PhantomData<H>,
+}
+
+impl<R, H> Reader<R, H>
+where
+    R: BufRead,
+    H: Hypercomplex + Identity + FromStr<Err = ComplexParseError>,
+    H::Scalar: FromStr,
+{
+    /// Creates a reader of tables with the given columns, checking the
+    /// header row against them if the format has one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if custom component names are not one per component.
+    pub fn new(reader: R, columns: &[&str], format: Format) -> Result<Self, CsvError> {
+        let mut reader = Self {
+            reader,
+            columns: columns.len(),
+            format,
+            line: 0,
+            marker: PhantomData,
+        };
+        if reader.format.header {
+            let expected = reader.format.header_cells(columns, H::DIM);
+            let (row, cells) = match reader.record()? {
+                Some(record) => record,
+                None => (reader.line + 1, Vec::new()),
+            };
+            reader.check_count(row, expected.len(), &cells)?;
+            for (n, (expected, actual)) in expected.into_iter().zip(cells).enumerate() {
+                if actual.trim() != expected {
+                    return Err(CsvError {
+                        row,
+                        column: n + 1,
+                        kind: CsvErrorKind::Header { expected, actual },
+                    });
+                }
+            }
+        }
+        Ok(reader)
+    }
+
+    fn check_count(&self, row: usize, expected: usize, cells: &[String]) -> Result<(), CsvError> {
+        if cells.len() == expected {
+            Ok(())
+        } else {
+            Err(CsvError {
+                row,
+                column: cells.len().min(expected) + 1,
+                kind: CsvErrorKind::CellCount {
+                    expected,
+                    actual: cells.len(),
+                },
+            })
+        }
+    }
+
+    /// Reads the next record that is not a blank line, returning the line it
+    /// starts on and its cells.
+    fn record(&mut self) -> Result<Option<(usize, Vec<String>)>, CsvError> {
+        let mut text = String::new();
+        let row = loop {
+            text.clear();
+            let row = self.line + 1;
+            if self.read_line(&mut text, row)? == 0 {
+                return Ok(None);
+            }
+            if !text.trim().is_empty() {
+                break row;
+            }
+        };
+        // a quoted cell may span lines
+        while text.matches('"').count() % 2 == 1 {
+            if self.read_line(&mut text, row)? == 0 {
+                return Err(CsvError {
+                    row,
+                    column: split(&text, self.format.delimiter).len(),
+                    kind: CsvErrorKind::UnterminatedQuote,
+                });
+            }
+        }
+        let text = text.strip_suffix('\n').unwrap_or(&text);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+
+        Ok(Some((row, split(text, self.format.delimiter))))
+    }
+
+    fn read_line(&mut self, text: &mut String, row: usize) -> Result<usize, CsvError> {
+        let len = self.reader.read_line(text).map_err(|err| CsvError {
+            row,
+            column: 1,
+            kind: CsvErrorKind::Io(err),
+        })?;
+        if len > 0 {
+            self.line += 1;
+        }
+        Ok(len)
+    }
+
+    fn row(&mut self) -> Result<Option<Vec<H>>, CsvError> {
+        let dim = H::DIM;
+        let (row, cells) = match self.record()? {
+            Some(record) => record,
+            None => return Ok(None),
+        };
+        self.check_count(row, self.format.cells(self.columns, dim), &cells)?;
+
+        let error = |n: usize, kind| CsvError {
+            row,
+            column: n + 1,
+            kind,
+        };
+        let values = match self.format.layout {
+            Layout::Text => cells
+                .iter()
+                .enumerate()
+                .map(|(n, cell)| {
+                    cell.parse()
+                        .map_err(|err| error(n, CsvErrorKind::Parse(err)))
+                })
+                .collect::<Result<_, _>>()?,
+            Layout::Components => cells
+                .chunks(dim)
+                .enumerate()
+                .map(|(column, chunk)| {
+                    let mut z = H::zero();
+                    for (m, (x, cell)) in scalars_mut(&mut z).iter_mut().zip(chunk).enumerate() {
+                        *x = cell.trim().parse().map_err(|_| {
+                            error(column * dim + m, CsvErrorKind::Number(cell.clone()))
+                        })?;
+                    }
+                    Ok(z)
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(Some(values))
+    }
+}
+
+impl<R, H> Iterator for Reader<R, H>
+where
+    R: BufRead,
+    H: Hypercomplex + Identity + FromStr<Err = ComplexParseError>,
+    H::Scalar: FromStr,
+{
+    type Item = Result<Vec<H>, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.row().transpose()
+    }
+}
+
+/// Splits a record into cells, removing the quotes of quoted cells.
+fn split(text: &str, delimiter: char) -> Vec<String> {
+    let mut cells = vec![String::new()];
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+    let mut start = true;
+
+    while let Some(c) = chars.next() {
+        let cell = cells.last_mut().unwrap();
+        match c {
+            '"' if start => quoted = true,
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' if quoted => quoted = false,
+            c if c == delimiter && !quoted => {
+                cells.push(String::new());
+                start = true;
+                continue;
+            }
+            c => cell.push(c),
+        }
+        start = false;
+    }
+    cells
+}
@@ -1,4 +1,8 @@
+// the operators on references are exercised on purpose
+#![allow(clippy::op_ref, clippy::needless_borrow)]
+
 use complex::*;
+use std::convert::TryFrom;
 
 #[test]
 fn test_add_two_complexf32() {
@@ -809,3 +813,125 @@ fn test_neg_octonionf64() {
 // let z1 = Complex::<f64>::new(0., 1.);
 // assert_eq!(z1.exp(), Complex::<f64>::new(1_f64.cos(), 1_f64.sin()));
 // }
+
+#[test]
+fn test_imaginary_constants_quaternionf32() {
+    let (i, j, k) = (Quaternionf32::i(), Quaternionf32::j(), Quaternionf32::k());
+    assert_eq!(i, complex![0., 1., 0., 0.]);
+    assert_eq!(j, complex![0., 0., 1., 0.]);
+    assert_eq!(k, complex![0., 0., 0., 1.]);
+    assert_eq!(i * j, k);
+    assert_eq!(i * j * k, -Quaternionf32::one());
+}
+
+#[test]
+fn test_imaginary_constants_sedenionf64() {
+    let one = Sedenionf64::one();
+    let i = Sedenionf64::i();
+    let j = Sedenionf64::j();
+    let k = Sedenionf64::k();
+    assert_eq!(
+        i,
+        Sedenionf64::from_slice(&[0., 1., 0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 0.])
+    );
+    assert_eq!(i * i, -one);
+    assert_eq!(i * j, k);
+    assert_eq!(j * k, i);
+    assert_eq!(k * i, j);
+}
+
+#[test]
+fn test_basis_octonionf64() {
+    let units: Vec<Octonionf64> = Octonionf64::basis_units().collect();
+    assert_eq!(units.len(), 8);
+    assert_eq!(units[0], Octonionf64::one());
+    for (n, unit) in units.iter().enumerate() {
+        let mut components = [0.; 8];
+        components[n] = 1.;
+        assert_eq!(*unit, Octonionf64::from_slice(&components));
+    }
+    for a in &units[1..] {
+        assert_eq!(a * a, -Octonionf64::one());
+        for b in &units[1..] {
+            if a != b {
+                assert_eq!(a * b, -(b * a));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_basis_out_of_range() {
+    assert_eq!(f64::basis(1), None);
+    assert_eq!(Complexf32::basis(2), None);
+    assert_eq!(
+        Trigintaduonionf64::basis(31),
+        Some(Trigintaduonionf64::e(31))
+    );
+    assert_eq!(Trigintaduonionf64::basis(32), None);
+}
+
+#[test]
+#[should_panic]
+fn test_e_out_of_range_quaternionf64() {
+    Quaternionf64::e(4);
+}
+
+#[test]
+fn test_index_octonionf64() {
+    let mut o = complex![1., 2., 3., 4., 5., 6., 7., 8.];
+    for n in 0..8 {
+        assert_eq!(o[n], (n + 1) as f64);
+    }
+    o[6] = -1.;
+    assert_eq!(o.re.im.re, 3.);
+    assert_eq!(o.im.im.re, -1.);
+    assert_eq!(o.to_vec(), vec![1., 2., 3., 4., 5., 6., -1., 8.]);
+    assert_eq!(o.to_array(), [1., 2., 3., 4., 5., 6., -1., 8.]);
+    assert_eq!(o.components().count(), Octonionf64::DIM);
+}
+
+#[test]
+fn test_index_complexf32() {
+    let mut z = Complex::<f32>::new(1., 2.);
+    z[1] += 1.;
+    assert_eq!(z.as_slice(), &[1., 3.]);
+    assert_eq!(z[0], z.re);
+}
+
+#[test]
+#[should_panic]
+fn test_index_out_of_range_quaternionf64() {
+    let q = complex![1., 2., 3., 4.];
+    let _ = q[4];
+}
+
+#[test]
+fn test_try_from_slice_octonionf64() {
+    let v = [1., 2., 3., 4., 5., 6., 7., 8.];
+    let o = complex![1., 2., 3., 4., 5., 6., 7., 8.];
+    assert_eq!(Octonionf64::try_from_slice(&v), Ok(o));
+    assert_eq!(Octonionf64::try_from(&v[..]), Ok(o));
+    assert_eq!(Octonionf64::try_from(v), Ok(o));
+    assert_eq!(
+        Octonionf64::try_from(&v[..6]),
+        Err(DimensionError {
+            expected: 8,
+            actual: 6
+        })
+    );
+    assert_eq!(
+        Octonionf64::try_from_slice(&[]),
+        Err(DimensionError {
+            expected: 8,
+            actual: 0
+        })
+    );
+}
+
+#[test]
+fn test_try_from_array_complexf32() {
+    assert_eq!(Complexf32::try_from([1., 2.]), Ok(complex![1., 2.]));
+    let err = Complexf32::try_from([1., 2., 3., 4.]).unwrap_err();
+    assert_eq!(err.to_string(), "Expected 2 components but found 4.");
+}
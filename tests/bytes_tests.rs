@@ -0,0 +1,101 @@
+use complex::bytes::{DecodeError, Endian, Header};
+use complex::*;
+use std::io;
+
+#[test]
+fn test_bytes_round_trip_octonionf64() {
+    let o = complex![1., -2., 0.5, -0., f64::INFINITY, 1e-300, 7., f64::MIN];
+    let le = o.to_le_bytes();
+    let be = o.to_be_bytes();
+
+    assert_eq!(le.len(), 64);
+    assert_eq!(le[8..16], (-2f64).to_le_bytes());
+    assert_eq!(be[8..16], (-2f64).to_be_bytes());
+    assert_eq!(Octonionf64::from_le_bytes(&le), Ok(o));
+    assert_eq!(Octonionf64::from_be_bytes(&be), Ok(o));
+    assert_eq!(
+        Octonionf64::from_le_bytes(&le[..60]),
+        Err(DecodeError::Length {
+            expected: 64,
+            actual: 60
+        })
+    );
+}
+
+#[test]
+fn test_bytes_stream_sedenionf32() {
+    let values: Vec<Sedenionf32> = (0..100)
+        .map(|n| {
+            let mut s = Sedenionf32::zero();
+            for m in 0..16 {
+                s[m] = (n * 16 + m) as f32 * 0.25;
+            }
+            s
+        })
+        .collect();
+    let mut stream = Vec::new();
+    for s in values.iter() {
+        s.write_to(&mut stream, Endian::Big).unwrap();
+    }
+    assert_eq!(stream.len(), 100 * 64);
+
+    let mut reader = &stream[..];
+    for s in values.iter() {
+        assert_eq!(
+            Sedenionf32::read_from(&mut reader, Endian::Big).unwrap(),
+            *s
+        );
+    }
+    let err = Sedenionf32::read_from(&mut reader, Endian::Big).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_bytes_large_trigintaduonionf64() {
+    let mut t = Trigintaduonionf64::zero();
+    for m in 0..32 {
+        t[m] = m as f64 - 16.;
+    }
+    let mut stream = Vec::new();
+    t.write_to(&mut stream, Endian::Little).unwrap();
+
+    assert_eq!(stream, t.to_le_bytes());
+    assert_eq!(
+        Trigintaduonionf64::read_from(&mut &stream[..], Endian::Little).unwrap(),
+        t
+    );
+}
+
+#[test]
+fn test_bytes_header() {
+    let header = Header::new::<Octonionf32>(Endian::Little);
+    assert_eq!(
+        header,
+        Header {
+            endian: Endian::Little,
+            kind: b'f',
+            size: 4,
+            dim: 8
+        }
+    );
+    assert_eq!(header.to_bytes(), *b"CDX1<f\x04\x00\x08\x00\x00\x00");
+    assert_eq!(
+        Header::new::<f64>(Endian::Big).to_bytes(),
+        *b"CDX1>f\x08\x00\x00\x00\x00\x01"
+    );
+    assert_eq!(Header::from_bytes(&header.to_bytes()), Ok(header));
+    assert_eq!(header.check::<Octonionf32>(), Ok(()));
+
+    let err = header.check::<Quaternionf64>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected 4 components of f8 but the header records 8 of f4."
+    );
+    assert_eq!(
+        Header::from_bytes(b"CDX1?f\x04\x00\x08\x00\x00\x00"),
+        Err(DecodeError::Magic)
+    );
+
+    let err = Header::read_from(&mut &b"not a header"[..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,181 @@
+#![allow(clippy::op_ref)]
+use complex::*;
+use std::f64::consts::PI;
+
+const EPSILON: f64 = 1e-12;
+
+fn assert_vector_eq(a: [f64; 3], b: [f64; 3]) {
+    for n in 0..3 {
+        assert!((a[n] - b[n]).abs() < EPSILON, "{:?} != {:?}", a, b);
+    }
+}
+
+fn assert_dual_eq(a: DualQuaternionf64, b: DualQuaternionf64) {
+    let d = a - b;
+    assert!(
+        d.real.abs() < EPSILON && d.dual.abs() < EPSILON,
+        "{:?} != {:?}",
+        a,
+        b
+    );
+}
+
+fn motion(axis: [f64; 3], angle: f64, translation: [f64; 3]) -> DualQuaternionf64 {
+    DualQuaternionf64::from_rotation_translation(
+        Quaternionf64::from_axis_angle(axis, angle),
+        translation,
+    )
+}
+
+#[test]
+fn test_dual_quaternion_arithmetic() {
+    let a = DualQuaternionf64::new(complex![1., 2., 3., 4.], complex![-1., 0.5, 0., 2.]);
+    let b = DualQuaternionf64::new(complex![0., 1., -1., 0.5], complex![3., 0., 1., -2.]);
+
+    assert_eq!(a + b, DualQuaternion::new(a.real + b.real, a.dual + b.dual));
+    assert_eq!(a - b, DualQuaternion::new(a.real - b.real, a.dual - b.dual));
+    assert_eq!(-a, DualQuaternion::new(-a.real, -a.dual));
+    assert_eq!(
+        a * b,
+        DualQuaternion::new(a.real * b.real, a.real * b.dual + a.dual * b.real)
+    );
+    assert_eq!(a * 2., DualQuaternion::new(a.real * 2., a.dual * 2.));
+    assert_eq!(a / 2., a * 0.5);
+    assert_eq!(&a * &b, a * b);
+    assert_eq!(&a + b, a + b);
+    assert_eq!(-&a, -a);
+    assert_eq!([a, b, a].iter().product::<DualQuaternionf64>(), a * b * a);
+    assert_eq!(
+        Vec::<DualQuaternionf64>::new()
+            .into_iter()
+            .product::<DualQuaternionf64>(),
+        DualQuaternion::one()
+    );
+
+    let mut c = a;
+    c *= b;
+    c += a;
+    c -= b;
+    c *= 3.;
+    c /= 2.;
+    assert_eq!(c, (a * b + a - b) * 3. / 2.);
+
+    // ε² = 0
+    let epsilon = DualQuaternionf64::new(Quaternionf64::zero(), Quaternionf64::one());
+    assert_eq!(epsilon * epsilon, DualQuaternion::zero());
+}
+
+#[test]
+fn test_dual_quaternion_conjugates() {
+    let q = DualQuaternionf64::new(complex![1., 2., 3., 4.], complex![5., 6., 7., 8.]);
+
+    assert_eq!(q.conj(), DualQuaternion::new(q.real.conj(), q.dual.conj()));
+    assert_eq!(q.dual_conj(), DualQuaternion::new(q.real, -q.dual));
+    assert_eq!(q.combined_conj(), q.dual_conj().conj());
+    assert_eq!(q.conj().conj(), q);
+    assert_eq!(q.combined_conj().combined_conj(), q);
+
+    // the quaternion conjugate inverts unit motions and reverses products
+    let a = motion([1., -2., 0.5], 1.1, [3., 0., -1.]);
+    let b = motion([0., 1., 1.], -0.4, [0.5, 2., 0.]);
+    assert_dual_eq(a * a.conj(), DualQuaternion::one());
+    assert_dual_eq((a * b).conj(), b.conj() * a.conj());
+    assert_dual_eq(
+        (a * b).combined_conj(),
+        b.combined_conj() * a.combined_conj(),
+    );
+    let p = [0.25, -4., 1.5];
+    assert_vector_eq(a.conj().transform_point(a.transform_point(p)), p);
+}
+
+#[test]
+fn test_dual_quaternion_rotation_translation() {
+    let r = Quaternionf64::from_axis_angle([2., 1., -1.], 2.3);
+    let t = [0.5, -3., 1.25];
+    let q = DualQuaternionf64::from_rotation_translation(r, t);
+    let (rotation, translation) = q.to_rotation_translation();
+
+    assert!((rotation - r).abs() < EPSILON);
+    assert_vector_eq(translation, t);
+    assert!((q.real.abs() - 1.).abs() < EPSILON && q.real.dot(&q.dual).abs() < EPSILON);
+
+    let p = [0.25, -4., 1.5];
+    let rotated = r.rotate_vector(p);
+    assert_vector_eq(q.transform_point(p), [0, 1, 2].map(|n| rotated[n] + t[n]));
+    assert_vector_eq((-q * 5.).transform_point(p), q.transform_point(p));
+
+    // products apply the right factor first
+    let a = motion([1., 0., 0.], PI / 3., [0., 1., 0.]);
+    let b = motion([0., 0., 1.], -PI / 4., [2., 0., 3.]);
+    assert_vector_eq(
+        (b * a).transform_point(p),
+        b.transform_point(a.transform_point(p)),
+    );
+
+    let q = DualQuaternionf32::from_rotation_translation(
+        Quaternionf32::from_axis_angle([0., 1., 0.], 0.5),
+        [1., 2., 3.],
+    );
+    let (_, [x, y, z]) = q.to_rotation_translation();
+    assert!((x - 1.).abs() < 1e-6 && (y - 2.).abs() < 1e-6 && (z - 3.).abs() < 1e-6);
+}
+
+#[test]
+fn test_dual_quaternion_normalize() {
+    let q = motion([1., 1., 1.], 0.9, [-1., 2., 0.5]);
+
+    assert_dual_eq((q * 4.).normalize(), q);
+    assert_dual_eq(q.normalize(), q);
+
+    // a dual part off the constraint is projected back onto it
+    let drifted = DualQuaternionf64::new(q.real * 1.01, q.dual + q.real * 0.02);
+    let p = drifted.normalize();
+    assert!((p.real.abs() - 1.).abs() < EPSILON);
+    assert!(p.real.dot(&p.dual).abs() < EPSILON);
+    assert_dual_eq(p.normalize(), p);
+}
+
+#[test]
+fn test_dual_quaternion_sclerp() {
+    let a = motion([1., -2., 0.5], 0.4, [1., 0., 2.]);
+    let b = motion([0., 3., 1.], 2.5, [-2., 1., 0.]);
+
+    assert_dual_eq(a.sclerp(&b, 0.), a);
+    assert_dual_eq(a.sclerp(&b, 1.), b);
+    assert_dual_eq(a.sclerp(&-b, 1.), b);
+
+    // the rotation follows slerp and the steps compose along one screw
+    for &t in [0.1, 0.5, 0.8].iter() {
+        let s = a.sclerp(&b, t);
+        assert!((s.real - a.real.slerp(&b.real, t)).abs() < EPSILON);
+        let step = a.conj() * b;
+        assert_dual_eq(s.conj() * a.sclerp(&b, t + 0.1), step.powf(0.1));
+    }
+    let q = a.conj() * b;
+    assert_dual_eq(q.powf(0.3) * q.powf(0.7), q);
+    assert_dual_eq(q.powf(-1.), q.conj());
+    assert_dual_eq(q.powf(2.), q * q);
+
+    // pure translations interpolate linearly, and nearly pure ones stay close
+    let a = motion([0., 0., 1.], 0., [0., 0., 0.]);
+    let b = motion([0., 0., 1.], 0., [2., -4., 6.]);
+    assert_dual_eq(
+        a.sclerp(&b, 0.25),
+        motion([0., 0., 1.], 0., [0.5, -1., 1.5]),
+    );
+    let c = motion([0., 0., 1.], 1e-10, [2., -4., 6.]);
+    let (rotation, translation) = a.sclerp(&c, 0.25).to_rotation_translation();
+    assert!((rotation - Quaternionf64::one()).abs() < 1e-10);
+    for (x, y) in translation.iter().zip([0.5, -1., 1.5].iter()) {
+        assert!((x - y).abs() < 1e-9);
+    }
+
+    // a screw about an axis away from the origin
+    let half_turn = Quaternionf64::from_axis_angle([0., 0., 1.], PI / 2.);
+    let start = motion([0., 0., 1.], 0., [0., 0., 0.]);
+    let end = DualQuaternionf64::from_rotation_translation(half_turn * half_turn, [2., 0., 4.]);
+    let mid = start.sclerp(&end, 0.5);
+    // the axis passes through (1, 0, z) and rises 2 per half turn
+    assert_vector_eq(mid.transform_point([1., 0., 0.]), [1., 0., 2.]);
+    assert_vector_eq(mid.transform_point([0., 0., 0.]), [1., -1., 2.]);
+}
@@ -0,0 +1,368 @@
+use complex::*;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+#[test]
+fn test_asin_complexf64() {
+    let z = Complex::<f64>::new(0.3, -1.2);
+    assert!((z.asin().sin() - z).abs_sq() < 1e-20);
+    assert!((complex![0.5, 0.0].asin() - complex![0.5_f64.asin(), 0.0]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_asin_complexf64_on_branch_cut() {
+    let w = complex![2.0, 0.0].asin();
+    assert!((w - complex![FRAC_PI_2, (2.0_f64 + 3.0_f64.sqrt()).ln()]).abs_sq() < 1e-20);
+    let w = complex![-2.0, 0.0].asin();
+    assert!((w - complex![-FRAC_PI_2, (2.0_f64 + 3.0_f64.sqrt()).ln()]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_acos_complexf64() {
+    let z = Complex::<f64>::new(-0.7, 0.4);
+    assert!((z.acos().cos() - z).abs_sq() < 1e-20);
+    assert!((z.acos() + z.asin() - complex![FRAC_PI_2, 0.0]).abs_sq() < 1e-20);
+    assert!((complex![-1.0, 0.0].acos() - complex![PI, 0.0]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_atan_complexf64() {
+    let z = Complex::<f64>::new(1.5, -0.25);
+    assert!((z.atan().tan() - z).abs_sq() < 1e-20);
+    assert!((complex![1.0, 0.0].atan() - complex![PI / 4., 0.0]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_inverse_trig_quaternionf64() {
+    // q = 0.2 + 0.5 u with the unit imaginary u = 0.6i + 0.8k
+    let q = complex![0.2, 0.3, 0.0, 0.4];
+    let u = complex![0.0, 0.6, 0.0, 0.8];
+    let z = Complex::<f64>::new(0.2, 0.5);
+    for (w, c) in [
+        (q.asin(), z.asin()),
+        (q.acos(), z.acos()),
+        (q.atan(), z.atan()),
+    ] {
+        assert!((w - (c.re + u * c.im)).abs_sq() < 1e-20);
+    }
+}
+
+#[test]
+fn test_inverse_trig_stays_in_plane_octonionf64() {
+    let o = complex![0.1, 0.0, 0.0, 0.0, 0.0, -0.3, 0.0, 0.4];
+    let w = o.asin();
+    assert_eq!(w.re.re.im, 0.0);
+    assert_eq!(w.re.im, Complex::<f64>::zero());
+    assert_eq!(w.im.re.re, 0.0);
+    assert_eq!(w.im.im.re, 0.0);
+    let z = Complex::<f64>::new(0.1, 0.5).asin();
+    assert!((w.re.re.re - z.re).abs() < 1e-10);
+    assert!((w.im.re.im + 0.6 * z.im).abs() < 1e-10);
+    assert!((w.im.im.im - 0.8 * z.im).abs() < 1e-10);
+}
+
+#[test]
+fn test_inverse_trig_real_quaternionf32() {
+    let q = Quaternionf32::one() * 0.5;
+    assert!((q.asin() - Quaternionf32::one() * 0.5_f32.asin()).abs_sq() < 1e-10);
+    assert!((q.acos() - Quaternionf32::one() * 0.5_f32.acos()).abs_sq() < 1e-10);
+    assert!((q.atan() - Quaternionf32::one() * 0.5_f32.atan()).abs_sq() < 1e-10);
+}
+
+#[test]
+fn test_inverse_hyperbolic_complexf64() {
+    let z = Complex::<f64>::new(-0.8, 1.7);
+    assert!((z.asinh().sinh() - z).abs_sq() < 1e-20);
+    assert!((z.acosh().cosh() - z).abs_sq() < 1e-20);
+    assert!((z.atanh().tanh() - z).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_inverse_hyperbolic_complexf64_on_real_axis() {
+    let ln_2_plus_sqrt_3 = (2.0_f64 + 3.0_f64.sqrt()).ln();
+    assert!((complex![-3.0, 0.0].asinh() - complex![(-3.0_f64).asinh(), 0.0]).abs_sq() < 1e-20);
+    assert!((complex![2.0, 0.0].acosh() - complex![2.0_f64.acosh(), 0.0]).abs_sq() < 1e-20);
+    assert!((complex![-2.0, 0.0].acosh() - complex![ln_2_plus_sqrt_3, PI]).abs_sq() < 1e-20);
+    assert!((complex![0.5, 0.0].atanh() - complex![0.5_f64.atanh(), 0.0]).abs_sq() < 1e-20);
+    assert!((complex![2.0, 0.0].atanh() - complex![3.0_f64.ln() / 2., FRAC_PI_2]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_inverse_hyperbolic_quaternionf64() {
+    let q = complex![0.2, 0.3, 0.0, 0.4];
+    let u = complex![0.0, 0.6, 0.0, 0.8];
+    let z = Complex::<f64>::new(0.2, 0.5);
+    for (w, c) in [
+        (q.asinh(), z.asinh()),
+        (q.acosh(), z.acosh()),
+        (q.atanh(), z.atanh()),
+    ] {
+        assert!((w - (c.re + u * c.im)).abs_sq() < 1e-20);
+    }
+    assert!((q.asinh().sinh() - q).abs_sq() < 1e-20);
+    assert!((q.acosh().cosh() - q).abs_sq() < 1e-20);
+    assert!((q.atanh().tanh() - q).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_inverse_hyperbolic_sedenionf64() {
+    let s = Sedenionf64::from_slice(&[
+        0.1, 0.2, -0.1, 0.0, 0.3, 0.0, 0.0, 0.1, -0.2, 0.0, 0.0, 0.1, 0.0, 0.0, 0.2, 0.1,
+    ]);
+    assert!((s.asinh().sinh() - s).abs_sq() < 1e-20);
+    assert!((s.acosh().cosh() - s).abs_sq() < 1e-20);
+    assert!((s.atanh().tanh() - s).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_inverse_hyperbolic_real_octonionf64() {
+    let o = Octonionf64::one() * 2.0;
+    let i = Octonionf64::i();
+    assert!((o.asinh() - Octonionf64::one() * 2.0_f64.asinh()).abs_sq() < 1e-20);
+    assert!((o.acosh() - Octonionf64::one() * 2.0_f64.acosh()).abs_sq() < 1e-20);
+    let w = o.atanh();
+    assert!((w - (Octonionf64::one() * 3.0_f64.ln() / 2. + i * FRAC_PI_2)).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_sqrt_complexf64() {
+    assert_eq!(complex![-4.0, 0.0].sqrt(), complex![0.0, 2.0]);
+    assert_eq!(complex![0.0, 2.0].sqrt(), complex![1.0, 1.0]);
+    assert_eq!(complex![0.0, -2.0].sqrt(), complex![1.0, -1.0]);
+    assert_eq!(Complexf64::zero().sqrt(), Complexf64::zero());
+}
+
+#[test]
+fn test_sqrt_quaternionf32() {
+    let q = complex![1.0_f32, -2.0, 3.0, 0.5];
+    assert!((q.sqrt() * q.sqrt() - q).abs_sq() < 1e-10);
+    assert!(q.sqrt().re.re > 0.0);
+    assert_eq!(Quaternionf32::zero().sqrt(), Quaternionf32::zero());
+}
+
+#[test]
+fn test_cbrt_octonionf64() {
+    let o = complex![-1.0, 2.0, 0.5, 3.0, 0.0, -1.0, 0.25, 1.0];
+    assert!((o.cbrt().powu(3) - o).abs_sq() < 1e-20);
+    assert_eq!(Octonionf64::zero().cbrt(), Octonionf64::zero());
+    assert!((complex![-8.0, 0.0].cbrt() - complex![1.0, 3.0_f64.sqrt()]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_nth_root_real_f64() {
+    let nth_root = <f64 as Functions<f64, Complexf64>>::nth_root;
+    assert_eq!(nth_root(&-27.0, 3), -3.0);
+    assert_eq!(nth_root(&16.0, 4), 2.0);
+    assert!(nth_root(&-16.0, 4).is_nan());
+}
+
+#[test]
+#[should_panic]
+fn test_nth_root_zero_complexf64() {
+    complex![1.0, 1.0].nth_root(0);
+}
+
+#[test]
+#[should_panic]
+fn test_all_nth_roots_zero_complexf64() {
+    complex![1.0, 1.0].all_nth_roots(0);
+}
+
+#[test]
+#[should_panic]
+fn test_all_nth_roots_zero_f64() {
+    let _: Vec<Complexf64> = 2.0.all_nth_roots(0);
+}
+
+#[test]
+fn test_all_nth_roots_complexf64() {
+    let z = complex![3.0, -4.0];
+    let roots: Vec<Complexf64> = z.all_nth_roots(6);
+    assert_eq!(roots.len(), 6);
+    assert!((roots[0] - z.nth_root(6)).abs_sq() < 1e-20);
+    for w in roots.iter() {
+        assert!((w.powu(6) - z).abs_sq() < 1e-20);
+    }
+    for k in 0..6 {
+        for l in (k + 1)..6 {
+            assert!((roots[k] - roots[l]).abs_sq() > 1e-3);
+        }
+    }
+}
+
+#[test]
+fn test_all_nth_roots_quaternionf64() {
+    let q = complex![0.5, 1.0, -1.0, 2.0];
+    let roots: Vec<Quaternionf64> = q.all_nth_roots(3);
+    assert_eq!(roots.len(), 3);
+    for w in roots.iter() {
+        assert!((w.powu(3) - q).abs_sq() < 1e-20);
+        // every root commutes with q
+        assert!((w * q - q * w).abs_sq() < 1e-20);
+    }
+}
+
+#[test]
+fn test_all_nth_roots_f64() {
+    let roots: Vec<Quaternionf64> = (-1.0_f64).all_nth_roots(2);
+    assert!((roots[0] - Quaternionf64::i()).abs_sq() < 1e-20);
+    assert!((roots[1] + Quaternionf64::i()).abs_sq() < 1e-20);
+    let roots: Vec<Complexf64> = 8.0_f64.all_nth_roots(3);
+    assert!((roots[0] - complex![2.0, 0.0]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_abs_quaternionf64() {
+    let q = complex![1.0, -1.0, 3.0, 5.0];
+    assert_eq!(q.abs(), 6.0);
+    assert_eq!(Quaternionf64::zero().abs(), 0.0);
+}
+
+#[test]
+fn test_arg_complexf64() {
+    assert_eq!(complex![1.0, 0.0].arg(), 0.0);
+    assert_eq!(complex![0.0, 1.0].arg(), FRAC_PI_2);
+    assert_eq!(complex![0.0, -1.0].arg(), FRAC_PI_2);
+    assert_eq!(complex![-1.0, 0.0].arg(), PI);
+}
+
+#[test]
+fn test_unit_imaginary_octonionf64() {
+    let o = complex![5.0, 0.0, 0.0, 0.0, 0.0, 3.0, -4.0, 0.0];
+    assert_eq!(
+        o.unit_imaginary(),
+        complex![0.0, 0.0, 0.0, 0.0, 0.0, 0.6, -0.8, 0.0]
+    );
+    assert_eq!(Octonionf64::one().unit_imaginary(), Octonionf64::zero());
+}
+
+#[test]
+fn test_polar_round_trip_quaternionf32() {
+    let q = complex![-1.0_f32, 0.5, 2.0, -0.25];
+    let (r, theta, axis) = q.to_polar();
+    assert!((r - q.abs()).abs() < 1e-6);
+    assert!((Quaternionf32::from_polar(r, theta, axis) - q).abs_sq() < 1e-10);
+}
+
+#[test]
+fn test_polar_round_trip_negative_real_sedenionf64() {
+    let s = Sedenionf64::one() * -3.0;
+    let (r, theta, axis) = s.to_polar();
+    assert_eq!((r, theta, axis), (3.0, PI, Sedenionf64::zero()));
+    assert!((Sedenionf64::from_polar(r, theta, axis) - s).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_exp_ln_use_polar_form_quaternionf64() {
+    let q: Quaternionf64 = complex![0.5, 1.0, -2.0, 2.0];
+    let (r, theta, axis) = q.to_polar();
+    assert!((q.ln() - (r.ln() + theta * axis)).abs_sq() < 1e-20);
+    assert!((q.ln().exp() - q).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_ln_negative_real_complexf64() {
+    assert!((complex![-1.0, 0.0].ln() - complex![0.0, PI]).abs_sq() < 1e-20);
+    assert!((complex![-2.0, 0.0].ln() - complex![2.0_f64.ln(), PI]).abs_sq() < 1e-20);
+    assert!((complex![-1.0, 0.0].ln().exp() - complex![-1.0, 0.0]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_ln_negative_real_quaternionf64() {
+    let q = Quaternionf64::one() * -1.0;
+    assert!((q.ln() - Quaternionf64::i() * PI).abs_sq() < 1e-20);
+    assert!((q.powf(0.5) - Quaternionf64::i()).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_ln_negative_real_octonionf64_and_sedenionf32() {
+    let o = Octonionf64::one() * -2.0;
+    assert!(
+        (o.ln() - (Octonionf64::one() * 2.0_f64.ln() + Octonionf64::i() * PI)).abs_sq() < 1e-20
+    );
+    assert!((o.ln().exp() - o).abs_sq() < 1e-20);
+
+    let s = -Sedenionf32::one();
+    assert!((s.ln() - Sedenionf32::i() * std::f32::consts::PI).abs_sq() < 1e-10);
+    assert_eq!(s.ln().to_vec()[2..], [0.0; 14]);
+}
+
+#[test]
+fn test_ln_axis_has_non_negative_i_component() {
+    // the axis of the imaginary part (-3, 0, 4) / 5 is flipped to (3, 0, -4) / 5
+    let q = complex![0.5, -3.0, 0.0, 4.0];
+    let u = complex![0.0, 0.6, 0.0, -0.8];
+    let theta = 5.0_f64.atan2(0.5);
+    assert!((q.ln() - (complex![25.25_f64.ln() / 2., 0.0, 0.0, 0.0] - u * theta)).abs_sq() < 1e-20);
+    assert!((q.ln_branch(1) - q.ln() - u * (2. * PI)).abs_sq() < 1e-20);
+
+    // which matches the complex plane for quaternions with only an i part
+    let z = complex![1.0, -2.0];
+    let w = complex![1.0, -2.0, 0.0, 0.0].ln_branch(1);
+    assert!((w - complex![z.ln_branch(1).re, z.ln_branch(1).im, 0.0, 0.0]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_ln_branch_complexf64() {
+    let z = complex![1.0, -1.0];
+    let ln_z = z.ln();
+    for k in -3..=3 {
+        let w = z.ln_branch(k);
+        assert!((w - ln_z - complex![0.0, 2. * PI * k as f64]).abs_sq() < 1e-20);
+        assert!((w.exp() - z).abs_sq() < 1e-20);
+    }
+}
+
+#[test]
+fn test_ln_branch_quaternionf64() {
+    let q = complex![0.5, 0.0, -3.0, 4.0];
+    let u = complex![0.0, 0.0, -0.6, 0.8];
+    assert!((q.ln_branch(2) - q.ln() - u * (4. * PI)).abs_sq() < 1e-20);
+    assert!((q.ln_branch(-1).exp() - q).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_ln_on_cut_complexf64() {
+    // a cut along the positive real axis at 2π puts the angle in (0, 2π]
+    let cut = Branch::Cut(2. * PI);
+    assert!((complex![0.0, -1.0].ln_on(cut) - complex![0.0, 3. * PI / 2.]).abs_sq() < 1e-20);
+    assert!((complex![0.0, 1.0].ln_on(cut) - complex![0.0, PI / 2.]).abs_sq() < 1e-20);
+    assert!((complex![1.0, 0.0].ln_on(cut) - complex![0.0, 2. * PI]).abs_sq() < 1e-20);
+    // a cut along the negative imaginary axis puts the angle in (-5π/2, -π/2]
+    let cut = Branch::Cut(-PI / 2.);
+    assert!((complex![-1.0, 0.0].ln_on(cut) - complex![0.0, -PI]).abs_sq() < 1e-20);
+    assert!((complex![1.0, 0.0].ln_on(cut) - complex![0.0, -2. * PI]).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_ln_on_principal_matches_ln_octonionf32() {
+    let o = complex![1.0_f32, -2.0, 0.5, 0.0, 3.0, 0.0, -1.0, 0.25];
+    assert_eq!(o.ln_on(Branch::Principal), o.ln());
+}
+
+#[test]
+fn test_powf_on_complexf64() {
+    let z = complex![0.0, 8.0];
+    let cube_roots: Vec<Complexf64> = z.all_nth_roots(3);
+    for k in 0..3 {
+        let w = z.powf_on(1. / 3., Branch::Sheet(k));
+        assert!((w - cube_roots[k as usize]).abs_sq() < 1e-20);
+    }
+}
+
+#[test]
+fn test_powz_on_quaternionf64() {
+    let q = complex![1.0, 1.0, 0.0, 0.0];
+    let p = complex![0.5, 0.0, 0.0, 0.0];
+    assert!((q.powz_on(p, Branch::Principal) - q.powz(p)).abs_sq() < 1e-20);
+    assert!((q.powz_on(p, Branch::Sheet(1)) + q.powz(p)).abs_sq() < 1e-20);
+}
+
+#[test]
+fn test_nth_root_on_sheets_match_all_nth_roots_quaternionf64() {
+    let q = complex![-2.0, 1.0, 0.5, -1.0];
+    let roots: Vec<Quaternionf64> = q.all_nth_roots(5);
+    for k in 0..5 {
+        assert!((q.nth_root_on(5, Branch::Sheet(k)) - roots[k as usize]).abs_sq() < 1e-20);
+    }
+    assert!((q.nth_root_on(5, Branch::Sheet(5)) - roots[0]).abs_sq() < 1e-20);
+}
@@ -0,0 +1,157 @@
+use complex::npy;
+use complex::*;
+use std::io;
+
+/// Builds a version 1.0 `.npy` file from a header dictionary and data.
+fn npy_file(dict: &str, data: &[u8]) -> Vec<u8> {
+    let mut file = b"\x93NUMPY\x01\x00".to_vec();
+    file.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    file.extend_from_slice(dict.as_bytes());
+    file.extend_from_slice(data);
+    file
+}
+
+#[test]
+fn test_npy_write_complexf64() {
+    let values = [complex![1., -2.], complex![0.5, 0.]];
+    let mut file = Vec::new();
+    npy::write(&mut file, &values).unwrap();
+
+    let dict = format!(
+        "{{'descr': '<c16', 'fortran_order': False, 'shape': (2,), }}{}\n",
+        " ".repeat(59)
+    );
+    let mut data = values[0].to_le_bytes();
+    data.extend(values[1].to_le_bytes());
+    assert_eq!(file, npy_file(&dict, &data));
+    assert_eq!(file.len(), 128 + 32);
+    assert_eq!(npy::read::<_, Complexf64>(&mut &file[..]).unwrap(), values);
+}
+
+#[test]
+fn test_npy_round_trip_quaternionf32() {
+    let values: Vec<Quaternionf32> = (0..3)
+        .map(|n| complex![n as f32, -1.5, 0.25, n as f32 * 2.])
+        .collect();
+    let mut file = Vec::new();
+    npy::write(&mut file, &values).unwrap();
+
+    assert_eq!(file.len(), 128 + 48);
+    assert!(String::from_utf8_lossy(&file[..128])
+        .contains("{'descr': '<f4', 'fortran_order': False, 'shape': (3, 4), }"));
+    assert_eq!(
+        npy::read::<_, Quaternionf32>(&mut &file[..]).unwrap(),
+        values
+    );
+
+    let path = std::env::temp_dir().join("complex_npy_round_trip.npy");
+    npy::save(&path, &values).unwrap();
+    assert_eq!(npy::load::<_, Quaternionf32>(&path).unwrap(), values);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_npy_round_trip_scalars() {
+    let values = [1.5, -2., 0.];
+    let mut file = Vec::new();
+    npy::write(&mut file, &values).unwrap();
+
+    assert!(String::from_utf8_lossy(&file[..128])
+        .contains("{'descr': '<f8', 'fortran_order': False, 'shape': (3,), }"));
+    assert_eq!(npy::read::<_, f64>(&mut &file[..]).unwrap(), values);
+    assert!(npy::read::<_, Complexf64>(&mut &file[..]).is_err());
+}
+
+#[test]
+fn test_npy_read_big_endian_fortran_order() {
+    // a (2, 4) array of big-endian float64 stored column by column
+    let columns = [[1., 5.], [2., 6.], [3., 7.], [4., 8.]];
+    let data: Vec<u8> = columns
+        .iter()
+        .flatten()
+        .flat_map(|x: &f64| x.to_be_bytes().to_vec())
+        .collect();
+    let file = npy_file(
+        "{\"descr\": \">f8\", \"shape\": (2, 4), \"fortran_order\": True}\n",
+        &data,
+    );
+
+    assert_eq!(
+        npy::read::<_, Quaternionf64>(&mut &file[..]).unwrap(),
+        vec![complex![1., 2., 3., 4.], complex![5., 6., 7., 8.]]
+    );
+}
+
+#[test]
+fn test_npy_read_complex_as_float_pairs() {
+    let data: Vec<u8> = [1f32, 2., 3., 4.]
+        .iter()
+        .flat_map(|x| x.to_le_bytes().to_vec())
+        .collect();
+    let file = npy_file(
+        "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 2), }",
+        &data,
+    );
+
+    assert_eq!(
+        npy::read::<_, Complexf32>(&mut &file[..]).unwrap(),
+        vec![complex![1., 2.], complex![3., 4.]]
+    );
+}
+
+#[test]
+fn test_npy_read_errors() {
+    let error = |file: &[u8]| {
+        let err = npy::read::<_, Quaternionf64>(&mut &file[..]).unwrap_err();
+        (err.kind(), err.to_string())
+    };
+    let invalid = |message: &str| (io::ErrorKind::InvalidData, message.to_string());
+
+    assert_eq!(error(b"PK\x03\x04 not numpy"), invalid("not a .npy file"));
+    assert_eq!(
+        error(&npy_file(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (1, 8), }",
+            &[0; 64]
+        )),
+        invalid("Expected 4 components but found 8.")
+    );
+    assert_eq!(
+        error(&npy_file(
+            "{'descr': '<c16', 'fortran_order': False, 'shape': (1,), }",
+            &[0; 16]
+        )),
+        invalid("cannot read an array of '<c16' with shape [1] as numbers of dimension 4 of f8")
+    );
+    assert_eq!(
+        error(&npy_file("{'descr': '<f8', 'shape': (1, 4), }", &[0; 32])),
+        invalid("missing key in the .npy header")
+    );
+    assert_eq!(
+        error(&npy_file(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (1, 4) ",
+            &[]
+        )),
+        invalid("expected `}` at byte 57 of the .npy header")
+    );
+    assert_eq!(
+        error(&npy_file(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 4), }",
+            &[0; 40]
+        ))
+        .0,
+        io::ErrorKind::UnexpectedEof
+    );
+    // a huge shape is not allocated before the data is read
+    assert_eq!(
+        error(&npy_file(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (100000000000000000, 4), }",
+            &[0; 64]
+        ))
+        .0,
+        io::ErrorKind::UnexpectedEof
+    );
+    let mut file = b"\x93NUMPY\x02\x00".to_vec();
+    file.extend_from_slice(&u32::MAX.to_le_bytes());
+    file.extend_from_slice(b"{'descr': '<f8'");
+    assert_eq!(error(&file).0, io::ErrorKind::UnexpectedEof);
+}
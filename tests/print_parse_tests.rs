@@ -1,3 +1,4 @@
+use complex::fmt::{ComplexParseError, ParseErrorKind, Units};
 use complex::*;
 
 #[test]
@@ -116,3 +117,462 @@ fn test_parse_octonionf64() {
         complex!(1.0_f64, 2.0_f64, 3.0_f64, 4.0_f64, 5.0_f64, 6.0_f64, 7.0_f64, 8.0_f64)
     );
 }
+
+#[test]
+fn test_parse_with_whitespace() {
+    let z: Complexf64 = " 1.5 + 2e-1i ".parse().unwrap();
+    assert_eq!(z, complex![1.5, 0.2]);
+    let q: Quaternionf32 = "-1 - 2i + 3.j - .5k".parse().unwrap();
+    assert_eq!(q, complex![-1., -2., 3., -0.5]);
+    let z = Complex::new(-1.25_f64, -3.0_f64);
+    assert_eq!(z.to_string().parse::<Complexf64>(), Ok(z));
+}
+
+#[test]
+fn test_parse_error_kinds() {
+    let cases = [
+        ("4b+1i", 1, "b", ParseErrorKind::UnexpectedCharacter),
+        ("", 0, "", ParseErrorKind::MissingComponent),
+        ("1 +", 3, "", ParseErrorKind::MissingComponent),
+        ("1 + 2i + i", 9, "i", ParseErrorKind::UnexpectedCharacter),
+        ("1e+400 + 1i", 0, "1e+400", ParseErrorKind::FloatOverflow),
+        ("1 + 2j", 2, "+ 2j", ParseErrorKind::WrongDimension),
+        ("2i + 3 - 1i", 7, "- 1i", ParseErrorKind::DuplicateComponent),
+    ];
+    for (s, offset, token, kind) in cases.iter() {
+        let err = s.parse::<Complexf64>().unwrap_err();
+        assert_eq!(
+            err,
+            ComplexParseError {
+                offset: *offset,
+                token: token.to_string(),
+                kind: *kind,
+            },
+            "parsing {:?}",
+            s
+        );
+    }
+}
+
+#[test]
+fn test_parse_error_octonionf64() {
+    let err = "(1+2i+3j+4k, 5+6i+7j+8)"
+        .parse::<Octonionf64>()
+        .unwrap_err();
+    assert_eq!(
+        (err.offset, err.kind),
+        (20, ParseErrorKind::DuplicateComponent)
+    );
+    let err = "(1+2i+3j+4k, 5+6i+7j+8k"
+        .parse::<Octonionf64>()
+        .unwrap_err();
+    assert_eq!(
+        (err.offset, err.kind),
+        (23, ParseErrorKind::MissingComponent)
+    );
+    let err = "(1+2i+3j+4k, 5+6i+7j+8k, 9)"
+        .parse::<Octonionf64>()
+        .unwrap_err();
+    assert_eq!((err.offset, err.token.as_str()), (23, ","));
+}
+
+#[test]
+fn test_parse_never_panics() {
+    for s in [
+        "",
+        " ",
+        "+",
+        "-",
+        ".",
+        "e",
+        "1e",
+        "1.e",
+        "i",
+        "(",
+        ")",
+        "((",
+        "(,)",
+        "(1,",
+        "1)",
+        "é",
+        "1é",
+        "(1+2i, é)",
+        "1++2",
+        "--1",
+        "1e+",
+        "1 2",
+    ]
+    .iter()
+    {
+        assert!(s.parse::<Complexf64>().is_err(), "parsing {:?}", s);
+        assert!(s.parse::<Quaternionf64>().is_err(), "parsing {:?}", s);
+        assert!(s.parse::<Sedenionf32>().is_err(), "parsing {:?}", s);
+    }
+}
+
+#[test]
+fn test_print_quaternionf64() {
+    let q = complex![1., -2., 3., -4.5];
+    assert_eq!(q.to_string(), "1 - 2i + 3j - 4.5k");
+}
+
+#[test]
+fn test_print_parse_octonionf64() {
+    let o = complex![1., 2., 3., 4., 5., 6., 7., 8.];
+    assert_eq!(
+        o.to_string(),
+        "1 + 2*e1 + 3*e2 + 4*e3 + 5*e4 + 6*e5 + 7*e6 + 8*e7"
+    );
+    for units in [Units::Standard, Units::Named, Units::Indexed].iter() {
+        let s = o.notation().units(*units).to_string();
+        assert_eq!(s.parse::<Octonionf64>(), Ok(o), "parsing {:?}", s);
+    }
+}
+
+#[test]
+fn test_print_parse_sedenionf32() {
+    let v: Vec<f32> = (0..16).map(|n| n as f32 - 7.5).collect();
+    let s = Sedenionf32::from_slice(&v);
+    assert_eq!(s.to_string().parse::<Sedenionf32>(), Ok(s));
+    assert!(s.to_string().ends_with(" + 7.5*e15"));
+}
+
+#[test]
+fn test_parse_terms_in_any_order() {
+    let o: Octonionf64 = "3kl - 2*e1 + 4 + 1.5l".parse().unwrap();
+    assert_eq!(o, complex![4., -2., 0., 0., 1.5, 0., 0., 3.]);
+    let q: Quaternionf64 = "2k + 1*e2 - 1i".parse().unwrap();
+    assert_eq!(q, complex![0., -1., 1., 2.]);
+    let s: Sedenionf64 = "1*e15 + 2i".parse().unwrap();
+    assert_eq!(s, Sedenionf64::e(15) + Sedenionf64::e(1) * 2.);
+}
+
+#[test]
+fn test_parse_exponent_or_unit() {
+    // an `e` after a number is always its exponent
+    let z: Complexf64 = "1e5".parse().unwrap();
+    assert_eq!(z, complex![1e5, 0.]);
+    let z: Complexf64 = "2.5e-3".parse().unwrap();
+    assert_eq!(z, complex![2.5e-3, 0.]);
+    let z: Complexf64 = "1e1".parse().unwrap();
+    assert_eq!(z, complex![10., 0.]);
+    let z: Complexf64 = "1.5e3-2e-1i".parse().unwrap();
+    assert_eq!(z, complex![1500., -0.2]);
+    let z: Complexf64 = "1.5e3 - 2e-1i".parse().unwrap();
+    assert_eq!(z, complex![1500., -0.2]);
+    let z: Complexf64 = "2e1i".parse().unwrap();
+    assert_eq!(z, complex![0., 20.]);
+    let q: Quaternionf64 = "1e2".parse().unwrap();
+    assert_eq!(q, complex![100., 0., 0., 0.]);
+    let o: Octonionf64 = "3e4 + 2e1kl".parse().unwrap();
+    assert_eq!(o, complex![30000., 0., 0., 0., 0., 0., 0., 20.]);
+
+    // indexed units follow a `*` or a space
+    assert_eq!("2e1".parse::<Octonionf64>(), Ok(Octonionf64::one() * 20.));
+    assert_eq!("2*e1".parse::<Octonionf64>(), Ok(Octonionf64::e(1) * 2.));
+    assert_eq!("2 e1".parse::<Octonionf64>(), Ok(Octonionf64::e(1) * 2.));
+    assert_eq!("2 * e1".parse::<Octonionf64>(), Ok(Octonionf64::e(1) * 2.));
+    assert_eq!("2e1 + 3 e1".parse::<Complexf64>(), Ok(complex![20., 3.]));
+    let z: Complexf64 = "2E1 + 2e-1*e1".parse().unwrap();
+    assert_eq!(z, complex![20., 0.2]);
+    let o: Octonionf64 = "1.5e2*e3 + 1e+2".parse().unwrap();
+    assert_eq!(o, complex![100., 0., 0., 150., 0., 0., 0., 0.]);
+    let z: Complexf64 = "1 - 2*e1".parse().unwrap();
+    assert_eq!(z, complex![1., -2.]);
+    let o = Octonionf64::e(5) * 4.;
+    let p: Octonionf64 = o.display_polar().to_string().parse().unwrap();
+    assert!((p - o).abs() < 1e-12, "{} parsed back to {}", o, p);
+}
+
+#[test]
+fn test_parse_wrong_units() {
+    for (s, token) in [("1 + 2l", "+ 2l"), ("3*e4", "3*e4"), ("1 - 2il", "- 2il")].iter() {
+        let err = s.parse::<Quaternionf64>().unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::WrongDimension);
+        assert_eq!(&err.token, token);
+    }
+    let err = "1 + 2kl".parse::<Sedenionf64>().unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::WrongDimension);
+    let err = "1*e99999999999999999999999"
+        .parse::<Sedenionf64>()
+        .unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::WrongDimension);
+    let err = "1 + 2*e1 + 3i".parse::<Octonionf64>().unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::DuplicateComponent);
+}
+
+#[test]
+fn test_print_precision_and_sign() {
+    let z = Complex::new(1.0_f32, -2.0_f32);
+    assert_eq!(format!("{:.3}", z), "1.000 - 2.000i");
+    assert_eq!(format!("{:+}", z), "+1 - 2i");
+    assert_eq!(format!("{:+}", -z), "-1 + 2i");
+    let o = Octonionf64::fill(0.125);
+    assert_eq!(
+        format!("{:.1}", o),
+        "0.1 + 0.1*e1 + 0.1*e2 + 0.1*e3 + 0.1*e4 + 0.1*e5 + 0.1*e6 + 0.1*e7"
+    );
+}
+
+#[test]
+fn test_print_width_and_alignment() {
+    let q = complex![1., -22.5, 0., 4.];
+    let all = q.notation().all_terms(true);
+    assert_eq!(format!("{:6.1}", q), "   1.0 -   22.5i +    4.0k");
+    assert_eq!(
+        format!("{:6.1}", all),
+        "   1.0 -   22.5i +    0.0j +    4.0k"
+    );
+    assert_eq!(format!("{:<4}|", all), "1    - 22.5i + 0   j + 4   k|");
+    assert_eq!(format!("{:*^5}", all), "**1** - 22.5*i + **0**j + **4**k");
+    assert_eq!(
+        format!("{:+06.1}", all),
+        "+001.0 - 0022.5i + 0000.0j + 0004.0k"
+    );
+    assert_eq!(format!("{:2}", q), " 1 - 22.5i +  4k");
+}
+
+#[test]
+fn test_print_aligned_table() {
+    let rows = [complex![1., 2., 3., 4.], complex![-10.25, 0.5, -3., 100.]];
+    let lines: Vec<String> = rows.iter().map(|q| format!("{:7.2}", q)).collect();
+    assert_eq!(lines[0], "   1.00 +    2.00i +    3.00j +    4.00k");
+    assert_eq!(lines[1], " -10.25 +    0.50i -    3.00j +  100.00k");
+    assert_eq!(lines[0].len(), lines[1].len());
+}
+
+#[test]
+fn test_print_exponent() {
+    let q = complex![1500., -0.25, 0., 1e-10];
+    assert_eq!(format!("{:e}", q), "1.5e3 - 2.5e-1i + 1e-10k");
+    assert_eq!(
+        format!("{:.1E}", q.notation().all_terms(true)),
+        "1.5E3 - 2.5E-1i + 0.0E0j + 1.0E-10k"
+    );
+    assert_eq!(format!("{:+e}", complex![2., 1.]), "+2e0 + 1e0i");
+    let o = Octonionf32::fill(300.);
+    assert_eq!(format!("{:e}", o).parse::<Octonionf32>(), Ok(o));
+    assert_eq!(format!("{:E}", o).parse::<Octonionf32>(), Ok(o));
+    let z = complex![1500., 2.];
+    assert_eq!(format!("{:e}", z), "1.5e3 + 2e0i");
+    assert_eq!(format!("{:e}", z).parse::<Complexf64>(), Ok(z));
+}
+
+#[test]
+fn test_print_omitted_zero_terms() {
+    assert_eq!(complex![0., 0.].to_string(), "0");
+    assert_eq!(format!("{:+.1}", Quaternionf64::zero()), "+0.0");
+    assert_eq!(complex![0., -2.].to_string(), "-2i");
+    assert_eq!(complex![0., 0., 0., 3.].to_string(), "3k");
+    assert_eq!(Sedenionf64::e(9).to_string(), "1*e9");
+    assert_eq!(
+        Octonionf64::from_slice(&[0., 0., 0., -1.5, 0., 0., 0., 0.])
+            .notation()
+            .all_terms(true)
+            .to_string(),
+        "0 + 0*e1 + 0*e2 - 1.5*e3 + 0*e4 + 0*e5 + 0*e6 + 0*e7"
+    );
+}
+
+#[test]
+fn test_print_negative_zero() {
+    assert_eq!(complex![-0., 0.].to_string(), "-0");
+    assert_eq!(complex![1., -0.].to_string(), "1 - 0i");
+    assert_eq!(complex![0., -0., 2., 0.].to_string(), "-0i + 2j");
+    let z: Complexf64 = "-0 - 0i".parse().unwrap();
+    assert!(z.re.is_sign_negative() && z.im.is_sign_negative());
+    assert_eq!(z.to_string(), "-0 - 0i");
+}
+
+#[test]
+fn test_print_parse_special_values() {
+    let inf = f64::INFINITY;
+    let q = complex![f64::NAN, -inf, inf, -1.];
+    assert_eq!(q.to_string(), "NaN - inf*i + inf*j - 1k");
+    assert_eq!(format!("{:.2e}", q), "NaN - inf*i + inf*j - 1.00e0k");
+    assert_eq!(format!("{:>5}", complex![inf, 1.]), "  inf +     1i");
+
+    let p: Quaternionf64 = q.to_string().parse().unwrap();
+    assert!(p.re.re.is_nan());
+    assert_eq!(p.as_slice()[1..], q.as_slice()[1..]);
+    let z: Complexf32 = "-Infinity + nan*i".parse().unwrap();
+    assert_eq!(z.re, f32::NEG_INFINITY);
+    assert!(z.im.is_nan());
+    let z: Complexf32 = "infi".parse().unwrap();
+    assert_eq!(z, complex![0., f32::INFINITY]);
+}
+
+#[test]
+fn test_print_parse_round_trip_octonionf64() {
+    let values = [0., -0., 1.5, -2., 1e300, -1e-300, f64::INFINITY];
+    for n in 0..values.len() {
+        let mut o = Octonionf64::zero();
+        for m in 0..8 {
+            o[m] = values[(n + m) % values.len()];
+        }
+        for s in [
+            o.to_string(),
+            format!("{:e}", o),
+            format!("{:E}", o),
+            o.notation().all_terms(true).to_string(),
+            o.notation().units(Units::Named).to_string(),
+        ]
+        .iter()
+        {
+            let p = s.parse::<Octonionf64>().unwrap();
+            for m in 0..8 {
+                assert_eq!(p[m].to_bits(), o[m].to_bits(), "parsing {:?}", s);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_print_latex() {
+    let q = complex![-1.25, 0., 2., -0.5];
+    assert_eq!(q.latex().to_string(), "-1.25 + 2j - 0.5k");
+    assert_eq!(format!("{:+.1}", q.latex()), "-1.2 + 2.0j - 0.5k");
+    assert_eq!(
+        q.latex().bold(true).all_terms(true).to_string(),
+        r"-1.25 + 0\mathbf{i} + 2\mathbf{j} - 0.5\mathbf{k}"
+    );
+    assert_eq!(format!("{:+}", Quaternionf64::zero().latex()), "+0");
+
+    let o = Octonionf64::e(5) * 3. + 1.;
+    assert_eq!(o.latex().to_string(), "1 + 3e_{5}");
+    assert_eq!(o.latex().units(Units::Named).to_string(), "1 + 3il");
+    assert_eq!(
+        o.latex().units(Units::Indexed).bold(true).to_string(),
+        r"1 + 3\mathbf{e}_{5}"
+    );
+    assert_eq!(
+        complex![f64::INFINITY, f64::NAN].latex().to_string(),
+        r"\infty + \mathrm{NaN}\,i"
+    );
+}
+
+#[test]
+fn test_print_mathml() {
+    let wrap = |body: &str| {
+        format!(
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>{}</mrow></math>",
+            body
+        )
+    };
+    let q = complex![0., -1., 0., 2.5];
+    assert_eq!(
+        q.mathml().to_string(),
+        wrap(
+            "<mo>-</mo><mn>1</mn><mo>&#x2062;</mo><mi>i</mi>\
+              <mo>+</mo><mn>2.5</mn><mo>&#x2062;</mo><mi>k</mi>"
+        )
+    );
+    assert_eq!(
+        format!("{:+.2}", complex![1., 0.].mathml()),
+        wrap("<mo>+</mo><mn>1.00</mn>")
+    );
+    let mut o = Octonionf32::zero();
+    o[6] = -f32::INFINITY;
+    assert_eq!(
+        o.mathml().bold(true).to_string(),
+        wrap(
+            "<mo>-</mo><mi>&#x221E;</mi><mo>&#x2062;</mo>\
+             <msub><mi mathvariant=\"bold\">e</mi><mn>6</mn></msub>"
+        )
+    );
+}
+
+#[test]
+fn test_print_polar_names_do_not_clash() {
+    use complex::fmt::*;
+
+    let q = complex![0., 0., 0., 2.];
+    let form: PolarForm<'_, Complex<f64>> = q.display_polar().style(PolarStyle::Angle);
+    assert_eq!(Polar::to_polar(&q).0, 2.);
+    assert_eq!(format!("{:.2}", form), "2.00∠1.57k");
+}
+
+#[test]
+fn test_print_polar() {
+    use complex::fmt::PolarStyle::Angle;
+
+    assert_eq!(
+        complex![-3., 0.].display_polar().to_string(),
+        format!("3·exp({}i)", std::f64::consts::PI)
+    );
+    assert_eq!(
+        complex![2., 0.].display_polar().style(Angle).to_string(),
+        "2∠0"
+    );
+    assert_eq!(
+        format!("{:.3}", complex![1., -1.].display_polar().style(Angle)),
+        "1.414∠-0.785"
+    );
+
+    let q = complex![0., -1., 0., 0.];
+    assert_eq!(format!("{:.2}", q.display_polar()), "1.00·exp(-1.57i)");
+    let q = complex![0., 0., 0., 2.];
+    assert_eq!(
+        format!("{:.2}", q.display_polar().style(Angle)),
+        "2.00∠1.57k"
+    );
+    let o = Octonionf64::e(5) * 4.;
+    assert_eq!(format!("{:.1}", o.display_polar()), "4.0·exp(1.6*e5)");
+    assert_eq!(
+        format!("{:.1}", o.display_polar().units(Units::Named)),
+        "4.0·exp(1.6il)"
+    );
+}
+
+#[test]
+fn test_parse_polar() {
+    let close = |a: Quaternionf64, b: Quaternionf64| (a - b).abs() < 1e-12;
+
+    let z: Complexf64 = "2∠0.5".parse().unwrap();
+    assert!((z - complex![2. * 0.5f64.cos(), 2. * 0.5f64.sin()]).abs() < 1e-12);
+    assert_eq!("2 * exp(-0.5i)".parse::<Complexf64>(), Ok(z.conj()));
+    assert_eq!("2·exp(0.5*i)".parse::<Complexf64>(), Ok(z));
+
+    let q: Quaternionf64 = "2·exp(1.5707963267948966·(0.6j + 0.8k))".parse().unwrap();
+    assert!(close(q, complex![0., 0., 1.2, 1.6]));
+    let q: Quaternionf64 = "2∠1.5707963267948966·(3j + 4k)".parse().unwrap();
+    assert!(close(q, complex![0., 0., 1.2, 1.6]));
+    let q: Quaternionf64 = "1∠3.141592653589793k".parse().unwrap();
+    assert!(close(q, -Quaternionf64::one()));
+
+    for q in [
+        complex![1., -2., 3., -4.],
+        complex![-1., 0., 0., 0.],
+        complex![0., 0., -5., 0.],
+    ]
+    .iter()
+    {
+        let p: Quaternionf64 = q.display_polar().to_string().parse().unwrap();
+        assert!(close(p, *q), "{} parsed back to {}", q, p);
+    }
+
+    let error = |s: &str| {
+        let err = s.parse::<Quaternionf64>().unwrap_err();
+        (err.offset, err.token, err.kind)
+    };
+    assert_eq!(
+        error("2·exp(0.5·(1 + 1k))"),
+        (12, "(1 + 1k)".to_string(), ParseErrorKind::InvalidAxis)
+    );
+    assert_eq!(
+        error("2∠0.5·(0i)"),
+        (9, "(0i)".to_string(), ParseErrorKind::InvalidAxis)
+    );
+    assert_eq!(
+        error("2·exp(0.5)"),
+        (10, ")".to_string(), ParseErrorKind::UnexpectedCharacter)
+    );
+    assert_eq!(
+        error("2∠0.5*e4"),
+        (4, "0.5*e4".to_string(), ParseErrorKind::WrongDimension)
+    );
+    assert_eq!(
+        error("2∠0.5i + 1"),
+        (9, "+".to_string(), ParseErrorKind::UnexpectedCharacter)
+    );
+}
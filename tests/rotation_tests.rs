@@ -0,0 +1,371 @@
+use complex::rotation::{EulerSequence, Frame};
+use complex::*;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+const SEQUENCES: [EulerSequence; 12] = [
+    EulerSequence::XYZ,
+    EulerSequence::XZY,
+    EulerSequence::YXZ,
+    EulerSequence::YZX,
+    EulerSequence::ZXY,
+    EulerSequence::ZYX,
+    EulerSequence::XYX,
+    EulerSequence::XZX,
+    EulerSequence::YXY,
+    EulerSequence::YZY,
+    EulerSequence::ZXZ,
+    EulerSequence::ZYZ,
+];
+
+const EPSILON: f64 = 1e-12;
+
+fn assert_vector_eq(a: [f64; 3], b: [f64; 3]) {
+    for n in 0..3 {
+        assert!((a[n] - b[n]).abs() < EPSILON, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn test_rotation_axis_angle_round_trip() {
+    for &(axis, angle) in [
+        ([1., 0., 0.], 0.3),
+        ([0., 2., -2.], 2.),
+        ([-1., 3., 0.5], PI - 1e-3),
+        ([0., 0., -1.], FRAC_PI_2),
+    ]
+    .iter()
+    {
+        let q = Quaternionf64::from_axis_angle(axis, angle);
+        let norm = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        let (a, theta) = q.to_axis_angle();
+
+        assert!((q.abs() - 1.).abs() < EPSILON);
+        assert!((theta - angle).abs() < EPSILON);
+        assert_vector_eq(a, axis.map(|x| x / norm));
+    }
+
+    // angles past π come back as the shorter rotation about the opposite axis
+    let q = Quaternionf64::from_axis_angle([0., 1., 0.], 1.5 * PI);
+    let (axis, angle) = q.to_axis_angle();
+    assert!((angle - FRAC_PI_2).abs() < EPSILON);
+    assert_vector_eq(axis, [0., -1., 0.]);
+
+    assert_eq!(
+        Quaternionf64::from_axis_angle([0., 0., 0.], 1.),
+        Quaternionf64::one()
+    );
+    assert_eq!(Quaternionf64::one().to_axis_angle(), ([1., 0., 0.], 0.));
+}
+
+#[test]
+fn test_rotation_rotate_vector() {
+    let q = Quaternionf64::from_axis_angle([1., 1., 1.], 2. * PI / 3.);
+
+    // a third of a turn about the diagonal cycles the axes
+    assert_vector_eq(q.rotate_vector([1., 0., 0.]), [0., 1., 0.]);
+    assert_vector_eq(q.rotate_vector([0., 1., 0.]), [0., 0., 1.]);
+    assert_vector_eq(q.rotate_vector([2., -3., 5.]), [5., 2., -3.]);
+    // scaling the quaternion does not scale the vector
+    assert_vector_eq((q * 3.).rotate_vector([2., -3., 5.]), [5., 2., -3.]);
+    assert_vector_eq((-q).rotate_vector([2., -3., 5.]), [5., 2., -3.]);
+
+    let q = Quaternionf32::from_axis_angle([0., 0., 1.], std::f32::consts::PI);
+    let [x, y, z] = q.rotate_vector([1., 2., 3.]);
+    assert!((x + 1.).abs() < 1e-6 && (y + 2.).abs() < 1e-6 && (z - 3.).abs() < 1e-6);
+}
+
+#[test]
+fn test_rotation_composition() {
+    let a = Quaternionf64::from_axis_angle([0.3, -1., 2.], 0.8);
+    let b = Quaternionf64::from_axis_angle([1., 0.5, 0.], -1.9);
+    let v = [0.25, -4., 1.5];
+
+    assert_vector_eq(
+        a.then(&b).rotate_vector(v),
+        b.rotate_vector(a.rotate_vector(v)),
+    );
+    assert_vector_eq(a.then(&a.inverse_rotation()).rotate_vector(v), v);
+    let q = complex![2., -1., 0.5, 3.];
+    assert!((q * q.inverse_rotation() - Quaternionf64::one()).abs() < EPSILON);
+    assert!((q.normalize().abs() - 1.).abs() < EPSILON);
+    assert_vector_eq(q.normalize().rotate_vector(v), q.rotate_vector(v));
+}
+
+#[test]
+fn test_rotation_between() {
+    for &(from, to) in [
+        ([1., 0., 0.], [0., 1., 0.]),
+        ([1., 2., 3.], [-3., 0.5, 2.]),
+        ([0., 0., 2.], [0., 0., 5.]),
+        ([1., 1., 0.], [-2., -2., 0.]),
+        ([0., 3., 0.], [0., -1., 0.]),
+    ]
+    .iter()
+    {
+        let q = Quaternionf64::rotation_between(from, to);
+        let scale = |v: [f64; 3]| {
+            let norm = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+            v.map(|x| x / norm)
+        };
+
+        assert!((q.abs() - 1.).abs() < EPSILON);
+        assert_vector_eq(scale(q.rotate_vector(from)), scale(to));
+    }
+    assert_eq!(
+        Quaternionf64::rotation_between([0., 0., 0.], [1., 0., 0.]),
+        Quaternionf64::one()
+    );
+
+    // opposite and nearly opposite vectors off the axes keep their precision
+    let from = [0.3f32, 0.5, 0.8];
+    let norm = from[0].hypot(from[1]).hypot(from[2]);
+    for &to in [
+        from.map(|x| -x),
+        from.map(|x| -2.5 * x),
+        [-0.3, -0.5 + 1e-3, -0.8],
+        [-0.3, -0.5 + 0.1, -0.8],
+    ]
+    .iter()
+    {
+        let q = Quaternionf32::rotation_between(from, to);
+        let rotated = q.rotate_vector(from);
+        let to_norm = to[0].hypot(to[1]).hypot(to[2]);
+
+        assert!(q.re.re < 0.1, "{}", q);
+        for n in 0..3 {
+            assert!(
+                (rotated[n] / norm - to[n] / to_norm).abs() < 1e-3,
+                "{:?}",
+                rotated
+            );
+        }
+    }
+}
+
+#[test]
+fn test_rotation_matrix_round_trip() {
+    for &(axis, angle) in [
+        ([1., 2., 3.], 0.4),
+        ([1., 0., 0.], PI),
+        ([0., 1., 0.], PI),
+        ([0., 0., 1.], PI),
+        ([1., -1., 0.5], PI - 1e-9),
+        ([0., 0., 0.], 0.),
+    ]
+    .iter()
+    {
+        let q = Quaternionf64::from_axis_angle(axis, angle);
+        let m = q.to_rotation_matrix();
+        let v = [0.25, -4., 1.5];
+        let mv = [0, 1, 2].map(|r| m[r][0] * v[0] + m[r][1] * v[1] + m[r][2] * v[2]);
+
+        assert_vector_eq(mv, q.rotate_vector(v));
+        assert_vector_eq((q * 2.).to_rotation_matrix()[1], m[1]);
+        let p = Quaternionf64::from_rotation_matrix(m);
+        assert!(p.re.re >= 0.);
+        assert!(
+            (p - q).abs() < EPSILON || (p + q).abs() < EPSILON,
+            "{} != {}",
+            p,
+            q
+        );
+    }
+}
+
+#[test]
+fn test_rotation_euler_round_trip() {
+    for &sequence in SEQUENCES.iter() {
+        for &frame in [Frame::Intrinsic, Frame::Extrinsic].iter() {
+            let symmetric = matches!(
+                sequence,
+                EulerSequence::XYX
+                    | EulerSequence::XZX
+                    | EulerSequence::YXY
+                    | EulerSequence::YZY
+                    | EulerSequence::ZXZ
+                    | EulerSequence::ZYZ
+            );
+            let middle = if symmetric { 1.2 } else { -0.7 };
+
+            for &angles in [[0.3, middle, -2.5], [-3., middle, 0.1]].iter() {
+                let q = Quaternionf64::from_euler(angles, sequence, frame);
+                let back = q.to_euler(sequence, frame);
+                assert_vector_eq(back, angles);
+                assert_vector_eq((q * -3.).to_euler(sequence, frame), angles);
+            }
+
+            // at gimbal lock the rotation is kept with a zero third angle
+            let locked = if symmetric {
+                [0., PI]
+            } else {
+                [FRAC_PI_2, -FRAC_PI_2]
+            };
+            for &middle in locked.iter() {
+                let q = Quaternionf64::from_euler([0.4, middle, 0.9], sequence, frame);
+                let angles = q.to_euler(sequence, frame);
+                let p = Quaternionf64::from_euler(angles, sequence, frame);
+
+                assert_eq!(angles[2], 0.);
+                assert!((p - q).abs() < 1e-6 || (p + q).abs() < 1e-6);
+            }
+        }
+    }
+
+    // near gimbal lock on f32 the lock is detected within rounding of the angle
+    for &(sequence, middle) in [
+        (EulerSequence::ZXZ, 1e-4f32),
+        (EulerSequence::YZY, std::f32::consts::PI - 1e-4),
+        (EulerSequence::ZYX, std::f32::consts::FRAC_PI_2 - 1e-4),
+    ]
+    .iter()
+    {
+        let q = Quaternionf32::from_euler([0.4, middle, 0.9], sequence, Frame::Intrinsic);
+        let angles = q.to_euler(sequence, Frame::Intrinsic);
+        let p = Quaternionf32::from_euler(angles, sequence, Frame::Intrinsic);
+
+        assert_eq!(angles[2], 0.);
+        assert!(
+            (p - q).abs() < 1e-3 || (p + q).abs() < 1e-3,
+            "{} != {}",
+            p,
+            q
+        );
+    }
+    let q = Quaternionf32::from_euler([0.4, 1e-2, 0.9], EulerSequence::ZXZ, Frame::Intrinsic);
+    let [a, b, c] = q.to_euler(EulerSequence::ZXZ, Frame::Intrinsic);
+    assert!((a - 0.4).abs() < 1e-3 && (b - 1e-2).abs() < 1e-5 && (c - 0.9).abs() < 1e-3);
+
+    let q = Quaternionf32::from_euler([0.5, -0.25, 2.], EulerSequence::YXZ, Frame::Intrinsic);
+    let [a, b, c] = q.to_euler(EulerSequence::YXZ, Frame::Intrinsic);
+    assert!((a - 0.5).abs() < 1e-5 && (b + 0.25).abs() < 1e-5 && (c - 2.).abs() < 1e-5);
+}
+
+#[test]
+fn test_rotation_euler_conventions() {
+    let degrees = |angles: [f64; 3]| angles.map(f64::to_radians);
+
+    // the direction cosine matrix of aircraft yaw ψ, pitch θ and roll φ
+    let (psi, theta, phi) = (0.7, -0.4, 2.1);
+    let q = Quaternionf64::from_euler([psi, theta, phi], EulerSequence::ZYX, Frame::Intrinsic);
+    let (s1, c1) = psi.sin_cos();
+    let (s2, c2) = theta.sin_cos();
+    let (s3, c3) = phi.sin_cos();
+    let m = [
+        [c2 * c1, s3 * s2 * c1 - c3 * s1, c3 * s2 * c1 + s3 * s1],
+        [c2 * s1, s3 * s2 * s1 + c3 * c1, c3 * s2 * s1 - s3 * c1],
+        [-s2, s3 * c2, c3 * c2],
+    ];
+    for (row, expected) in q.to_rotation_matrix().iter().zip(m.iter()) {
+        assert_vector_eq(*row, *expected);
+    }
+    assert_vector_eq(
+        Quaternionf64::from_rotation_matrix(m).to_euler(EulerSequence::ZYX, Frame::Intrinsic),
+        [psi, theta, phi],
+    );
+    assert_vector_eq(
+        q.to_euler(EulerSequence::XYZ, Frame::Extrinsic),
+        [phi, theta, psi],
+    );
+
+    // aircraft yaw, pitch and roll: a yaw of 90° turns the nose from x to y
+    let q = Quaternionf64::from_euler(degrees([90., 0., 0.]), EulerSequence::ZYX, Frame::Intrinsic);
+    assert_vector_eq(q.rotate_vector([1., 0., 0.]), [0., 1., 0.]);
+    // a positive pitch then lowers the nose
+    let q = Quaternionf64::from_euler(
+        degrees([90., 30., 0.]),
+        EulerSequence::ZYX,
+        Frame::Intrinsic,
+    );
+    assert_vector_eq(q.rotate_vector([1., 0., 0.]), [0., 3f64.sqrt() / 2., -0.5]);
+}
+
+#[test]
+fn test_rotation_slerp_nlerp() {
+    let q = Quaternionf64::from_axis_angle([1., -2., 0.5], 0.4);
+    let p = Quaternionf64::from_axis_angle([0., 3., 1.], 2.5);
+    let angle =
+        |a: &Quaternionf64, b: &Quaternionf64| a.inverse_rotation().then(b).to_axis_angle().1;
+    let theta = angle(&q, &p);
+
+    for &t in [0., 0.1, 0.5, 0.9, 1.].iter() {
+        let s = q.slerp(&p, t);
+        let n = q.nlerp(&p, t);
+
+        // constant angular velocity along the arc, through the same rotations
+        assert!((s.abs() - 1.).abs() < EPSILON);
+        assert!((angle(&q, &s) - t * theta).abs() < EPSILON);
+        assert!((angle(&q, &n) + angle(&n, &p) - theta).abs() < EPSILON);
+        // the sign of either end does not change the path
+        assert!((q.slerp(&-p, t) - s).abs() < EPSILON);
+        assert!(((-q).slerp(&p, t) + s).abs() < EPSILON);
+        assert!((q.geodesic(&p, t) - s).abs() < EPSILON);
+    }
+    assert!((q.slerp(&p, 1.) - p).abs() < EPSILON);
+    assert!((q.nlerp(&p, 1.) - p).abs() < EPSILON);
+
+    // the geodesic keeps the signs and takes the long way round
+    let (axis, long) = q
+        .inverse_rotation()
+        .then(&q.geodesic(&-p, 0.5))
+        .to_axis_angle();
+    assert!((long - (PI - theta / 2.)).abs() < EPSILON);
+    assert_vector_eq(
+        axis.map(|x| -x),
+        q.inverse_rotation().then(&p).to_axis_angle().0,
+    );
+    assert!((q.geodesic(&p, 2.) - p * q.inverse_rotation() * p).abs() < EPSILON);
+
+    // nearly equal rotations
+    let r = Quaternionf64::from_axis_angle([1., -2., 0.5], 0.4 + 1e-9);
+    assert!(
+        (q.slerp(&r, 0.5) - Quaternionf64::from_axis_angle([1., -2., 0.5], 0.4 + 5e-10)).abs()
+            < EPSILON
+    );
+    assert_eq!(q.slerp(&q, 0.3), q);
+
+    let q = Quaternionf32::from_axis_angle([0., 0., 1.], 0.25);
+    let p = Quaternionf32::from_axis_angle([0., 0., 1.], -2.75);
+    let (axis, angle) = q.slerp(&p, 0.5).to_axis_angle();
+    assert!((axis[2] + 1.).abs() < 1e-6 && (angle - 1.25).abs() < 1e-5);
+}
+
+#[test]
+fn test_rotation_squad() {
+    let keys: Vec<Quaternionf64> = [
+        ([1., 0., 0.], 0.),
+        ([1., 1., 0.], 0.8),
+        ([0., 1., 2.], -1.5),
+        ([2., -1., 1.], 2.),
+        ([0., 0., 1.], 1.),
+    ]
+    .iter()
+    .map(|&(axis, angle)| Quaternionf64::from_axis_angle(axis, angle))
+    .collect();
+    // make neighbouring keyframes take the shortest arcs
+    let mut aligned = vec![keys[0]];
+    for key in &keys[1..] {
+        let last = aligned[aligned.len() - 1];
+        aligned.push(last.align(key));
+    }
+    let control =
+        |n: usize| Quaternionf64::squad_control(&aligned[n - 1], &aligned[n], &aligned[n + 1]);
+    let curve =
+        |n: usize, t: f64| aligned[n].squad(&control(n), &control(n + 1), &aligned[n + 1], t);
+
+    for n in 1..3 {
+        assert!((curve(n, 0.) - aligned[n]).abs() < EPSILON);
+        assert!((curve(n, 1.) - aligned[n + 1]).abs() < EPSILON);
+        assert!((curve(n, 0.3).abs() - 1.).abs() < EPSILON);
+    }
+    // the angular velocity is continuous across the middle keyframe
+    let h = 1e-5;
+    let before = (curve(1, 1.) - curve(1, 1. - h)) / h;
+    let after = (curve(2, h) - curve(2, 0.)) / h;
+    assert!((before - after).abs() < 1e-4, "{} != {}", before, after);
+
+    // keyframes at constant angular velocity give slerp
+    let q = |angle| Quaternionf32::from_axis_angle([0., 1., 1.], angle);
+    let a = Quaternionf32::squad_control(&q(0.), &q(0.7), &q(1.4));
+    let b = Quaternionf32::squad_control(&q(0.7), &q(1.4), &q(2.1));
+    assert!((q(0.7).squad(&a, &b, &q(1.4), 0.25) - q(0.875)).abs() < 1e-5);
+}
@@ -0,0 +1,174 @@
+use complex::*;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+/// A real type from outside the crate, forwarding to `f64`.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+struct Real(f64);
+
+macro_rules! forward_op {
+    ($imp:ident, $method:ident) => {
+        impl $imp for Real {
+            type Output = Real;
+            fn $method(self, other: Real) -> Real {
+                Real(self.0.$method(other.0))
+            }
+        }
+    };
+}
+
+forward_op!(Add, add);
+forward_op!(Sub, sub);
+forward_op!(Mul, mul);
+forward_op!(Div, div);
+forward_op!(Rem, rem);
+
+impl Neg for Real {
+    type Output = Real;
+    fn neg(self) -> Real {
+        Real(-self.0)
+    }
+}
+
+macro_rules! forward_fn {
+    ($($method:ident),*) => {
+        $(
+            fn $method(self) -> Self {
+                Real(self.0.$method())
+            }
+        )*
+    };
+}
+
+impl Scalar for Real {
+    fn from_f64(num: f64) -> Self {
+        Real(num)
+    }
+
+    fn pi() -> Self {
+        Real(std::f64::consts::PI)
+    }
+
+    fn is_finite(self) -> bool {
+        self.0.is_finite()
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        Real(self.0.copysign(sign.0))
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        Real(self.0.hypot(other.0))
+    }
+
+    fn powf(self, num: Self) -> Self {
+        Real(self.0.powf(num.0))
+    }
+
+    fn powi(self, num: i32) -> Self {
+        Real(self.0.powi(num))
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        Real(self.0.atan2(other.0))
+    }
+
+    forward_fn!(
+        abs, floor, ceil, round, trunc, fract, sqrt, cbrt, exp, ln, sin, cos, tan, asin, acos,
+        atan, sinh, cosh, tanh, asinh, acosh, atanh
+    );
+}
+
+impl_scalar_ops!(Real);
+
+impl fmt::Display for Real {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for Real {
+    type Err = std::num::ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Real)
+    }
+}
+
+type Quaternion = Complex<Complex<Real>>;
+
+fn quaternion(w: f64, x: f64, y: f64, z: f64) -> Quaternion {
+    complex![Real(w), Real(x), Real(y), Real(z)]
+}
+
+/// Converts to the same quaternion of `f64`, to compare with the built-in type.
+fn to_f64(q: &Quaternion) -> Quaternionf64 {
+    complex![q.re.re.0, q.re.im.0, q.im.re.0, q.im.im.0]
+}
+
+fn assert_close(q: &Quaternion, expected: Quaternionf64) {
+    assert!(
+        (to_f64(q) - expected).abs() < 1e-12,
+        "{} != {}",
+        q,
+        expected
+    );
+}
+
+#[test]
+fn test_custom_scalar_arithmetic() {
+    let a = quaternion(1., 2., -3., 0.5);
+    let b = quaternion(-0.5, 0., 4., 1.);
+    let (a64, b64) = (to_f64(&a), to_f64(&b));
+
+    assert_eq!(to_f64(&(a + b)), a64 + b64);
+    assert_eq!(to_f64(&(a - b)), a64 - b64);
+    assert_eq!(to_f64(&(a * b)), a64 * b64);
+    assert_eq!(to_f64(&-a), -a64);
+    assert_close(&(a / b), a64 / b64);
+    assert_eq!(to_f64(&(Real(2.) * a)), 2. * a64);
+    assert_eq!(to_f64(&(a - Real(1.))), a64 - 1.);
+    assert_eq!(Real(3.) - a, -(a - Real(3.)));
+    assert_eq!(a.abs(), Real(a64.abs()));
+    assert_eq!(a * a.conj(), Quaternion::one() * Real(a64.abs_sq()));
+    assert_eq!([a, b].iter().product::<Quaternion>(), a * b);
+
+    let mut c = a;
+    c *= b;
+    c += Real(1.);
+    assert_eq!(c, a * b + Real(1.));
+}
+
+#[test]
+fn test_custom_scalar_functions() {
+    let q = quaternion(0.5, -1., 0.25, 2.);
+    let q64 = to_f64(&q);
+
+    assert_close(&q.exp(), q64.exp());
+    assert_close(&q.ln(), q64.ln());
+    assert_close(&q.sqrt(), q64.sqrt());
+    assert_close(&q.sin(), q64.sin());
+    assert_close(&q.tanh(), q64.tanh());
+    assert_close(&q.powf(Real(0.7)), q64.powf(0.7));
+    assert_close(&q.ln().exp(), q64);
+    assert_close(
+        &quaternion(-1., 0., 0., 0.).ln(),
+        (-Quaternionf64::one()).ln(),
+    );
+}
+
+#[test]
+fn test_custom_scalar_print_parse() {
+    let q = quaternion(1., -2., 0.5, 0.);
+    let text = q.to_string();
+
+    assert_eq!(text, to_f64(&q).to_string());
+    assert_eq!(text.parse::<Quaternion>(), Ok(q));
+    assert_eq!(format!("{:.1}", q), "1.0 - 2.0i + 0.5j");
+    assert_eq!(
+        "2 - 3e1j".parse::<Quaternion>(),
+        Ok(quaternion(2., 0., -30., 0.))
+    );
+    assert!("1 + 2q".parse::<Quaternion>().is_err());
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "serde")]
+use complex::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    #[serde(with = "complex::serialize::flat")]
+    flat: Octonionf32,
+    #[serde(with = "complex::serialize::nested")]
+    nested: Quaternionf64,
+    plain: Complexf64,
+}
+
+#[test]
+fn test_serde_nested_complexf64() {
+    let z = complex![1.5, -2.];
+    let json = serde_json::to_string(&z).unwrap();
+
+    assert_eq!(json, r#"{"re":1.5,"im":-2.0}"#);
+    assert_eq!(serde_json::from_str::<Complexf64>(&json).unwrap(), z);
+    assert!(serde_json::from_str::<Complexf64>(r#"{"re":1.5}"#).is_err());
+    assert!(serde_json::from_str::<Complexf64>(r#"{"re":1.5,"im":2,"jm":3}"#).is_err());
+}
+
+#[test]
+fn test_serde_nested_quaternionf64() {
+    let q = complex![1., 2., 3., 4.];
+    let json = serde_json::to_string(&q).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"re":{"re":1.0,"im":2.0},"im":{"re":3.0,"im":4.0}}"#
+    );
+    assert_eq!(serde_json::from_str::<Quaternionf64>(&json).unwrap(), q);
+    // a complex number where a quaternion is expected has the wrong depth
+    assert!(serde_json::from_str::<Quaternionf64>(r#"{"re":1.0,"im":2.0}"#).is_err());
+}
+
+#[test]
+fn test_serde_record() {
+    let record = Record {
+        flat: complex![1., 2., 3., 4., 5., 6., 7., 8.],
+        nested: complex![0., -1., 0.5, 0.],
+        plain: complex![3., 4.],
+    };
+    let json = serde_json::to_string(&record).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"flat":[1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0],"#.to_string()
+            + r#""nested":{"re":{"re":0.0,"im":-1.0},"im":{"re":0.5,"im":0.0}},"#
+            + r#""plain":{"re":3.0,"im":4.0}}"#
+    );
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+}
+
+#[test]
+fn test_serde_flat_dimension() {
+    #[derive(Debug, Deserialize)]
+    struct Flat(#[serde(with = "complex::serialize::flat")] Quaternionf64);
+
+    let Flat(q) = serde_json::from_str("[1, 2, 3, 4]").unwrap();
+    assert_eq!(q, complex![1., 2., 3., 4.]);
+
+    let err = serde_json::from_str::<Flat>("[1, 2, 3]").unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Expected 4 components but found 3."));
+    let err = serde_json::from_str::<Flat>("[1, 2, 3, 4, 5, 6]").unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Expected 4 components but found 6."));
+    assert!(serde_json::from_str::<Flat>(r#"{"re":1}"#).is_err());
+}
@@ -0,0 +1,106 @@
+use complex::statistics::{angular_distance, covariance, karcher_mean, markley_mean};
+use complex::*;
+use std::f64::consts::PI;
+
+const EPSILON: f64 = 1e-12;
+
+fn about_z(angle: f64) -> Quaternionf64 {
+    Quaternionf64::from_axis_angle([0., 0., 1.], angle)
+}
+
+#[test]
+fn test_statistics_angular_distance() {
+    let a = Quaternionf64::from_axis_angle([1., 2., -1.], 0.7);
+    let b = Quaternionf64::from_axis_angle([0., 1., 3.], -2.2);
+    let d = angular_distance(&a, &b);
+
+    assert_eq!(angular_distance(&a, &a), 0.);
+    assert!((angular_distance(&b, &a) - d).abs() < EPSILON);
+    assert!((angular_distance(&-a, &(b * 3.)) - d).abs() < EPSILON);
+    assert!((d - a.inverse_rotation().then(&b).to_axis_angle().1).abs() < EPSILON);
+    assert!((angular_distance(&about_z(-1.5), &about_z(2.)) - (2. * PI - 3.5)).abs() < EPSILON);
+    assert!((angular_distance(&Quaternionf64::one(), &about_z(PI)) - PI).abs() < EPSILON);
+}
+
+#[test]
+fn test_statistics_means_about_one_axis() {
+    // signs flipped at random do not matter
+    let samples = [about_z(0.), -about_z(0.), about_z(2.)];
+
+    // the Karcher mean averages the angles, Markley's the unit vectors
+    let karcher = karcher_mean(&samples).unwrap();
+    assert!((karcher - about_z(2. / 3.)).abs() < EPSILON, "{}", karcher);
+    let markley = markley_mean(&samples).unwrap();
+    let angle = 2f64.sin().atan2(2. + 2f64.cos());
+    assert!((markley - about_z(angle)).abs() < EPSILON, "{}", markley);
+
+    assert_eq!(karcher_mean(&[]), None);
+    assert_eq!(markley_mean(&[-about_z(1.)]), Some(about_z(1.)));
+    assert!((karcher_mean(&[-about_z(1.) * 2.]).unwrap() - about_z(1.)).abs() < EPSILON);
+}
+
+#[test]
+fn test_statistics_symmetric_cluster() {
+    let center = Quaternionf64::from_axis_angle([1., -1., 2.], 2.5);
+    let delta = 0.2;
+    let mut samples = Vec::new();
+    for axis in [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]].iter() {
+        for &sign in [1., -1.].iter() {
+            let offset = Quaternionf64::from_axis_angle(*axis, sign * delta);
+            samples.push(center * offset * sign);
+        }
+    }
+
+    for mean in [markley_mean(&samples), karcher_mean(&samples)].iter() {
+        let mean = mean.unwrap();
+        assert!(angular_distance(&mean, &center) < EPSILON);
+        assert!(mean.re.re >= 0.);
+    }
+
+    let c = covariance(&samples, &center).unwrap();
+    for (n, row) in c.iter().enumerate() {
+        for (m, &entry) in row.iter().enumerate() {
+            let expected = if n == m { delta * delta / 3. } else { 0. };
+            assert!((entry - expected).abs() < EPSILON, "{:?}", c);
+        }
+    }
+    assert_eq!(covariance(&[], &center), None);
+}
+
+#[test]
+fn test_statistics_karcher_mean_is_stationary() {
+    let samples: Vec<Quaternionf64> = (0..20)
+        .map(|n: usize| {
+            let x = n as f64;
+            let axis = [(x * 1.3).sin(), (x * 0.7).cos(), 0.5 + (x * 2.1).sin()];
+            let q = Quaternionf64::from_axis_angle(axis, 0.1 + 0.05 * x);
+            if n % 3 == 0 {
+                -q
+            } else {
+                q
+            }
+        })
+        .collect();
+    let mean = karcher_mean(&samples).unwrap();
+
+    // the rotation vectors of the samples about the mean average to zero
+    let mut sum = [0.; 3];
+    for q in &samples {
+        let v = (mean.inverse_rotation() * mean.align(q)).ln().vector();
+        for (s, x) in sum.iter_mut().zip(v.iter()) {
+            *s += x;
+        }
+    }
+    assert!(sum.iter().all(|s| s.abs() < 1e-10), "{:?}", sum);
+
+    // and no nearby rotation is closer to the samples on average
+    let cost =
+        |m: &Quaternionf64| -> f64 { samples.iter().map(|q| angular_distance(m, q).powi(2)).sum() };
+    for axis in [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]].iter() {
+        for &angle in [1e-3, -1e-3].iter() {
+            assert!(cost(&mean) < cost(&(mean * Quaternionf64::from_axis_angle(*axis, angle))));
+        }
+    }
+    let markley = markley_mean(&samples).unwrap();
+    assert!(angular_distance(&mean, &markley) < 0.01);
+}
@@ -0,0 +1,175 @@
+use complex::fmt::{ComplexParseError, ParseErrorKind};
+use complex::table::{ComponentNames, CsvError, CsvErrorKind, Format, Layout, Reader, Writer};
+use complex::*;
+
+fn write<H>(columns: &[&str], format: Format, rows: &[Vec<H>]) -> String
+where
+    H: Hypercomplex + std::fmt::Display,
+    H::Scalar: std::fmt::Display,
+{
+    let mut writer = Writer::new(Vec::new(), columns, format).unwrap();
+    for row in rows {
+        writer.write_row(row).unwrap();
+    }
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+fn read<H>(text: &str, columns: &[&str], format: Format) -> Result<Vec<Vec<H>>, CsvError>
+where
+    H: Hypercomplex + Identity + std::str::FromStr<Err = ComplexParseError>,
+    H::Scalar: std::str::FromStr,
+{
+    Reader::new(text.as_bytes(), columns, format)?.collect()
+}
+
+#[test]
+fn test_table_text_complexf64() {
+    let rows = vec![
+        vec![complex![1., -2.], complex![0., 0.5]],
+        vec![complex![-0., 3.], complex![f64::INFINITY, 1.]],
+    ];
+    let text = write(&["z", "w, shifted"], Format::text(), &rows);
+
+    assert_eq!(text, "z,\"w, shifted\"\n1 - 2i,0.5i\n-0 + 3i,inf + 1i\n");
+    let read = read::<Complexf64>(&text, &["z", "w, shifted"], Format::text()).unwrap();
+    assert_eq!(read, rows);
+    assert_eq!(read[1][0].re.to_bits(), (-0f64).to_bits());
+}
+
+#[test]
+fn test_table_components_names() {
+    let rows = vec![vec![complex![1.5f32, -2.]]];
+    let format = Format::components();
+
+    assert_eq!(write(&[""], format.clone(), &rows), "re,im\n1.5,-2\n");
+    assert_eq!(
+        write(
+            &["z"],
+            format.clone().component_names(ComponentNames::Indexed),
+            &rows
+        ),
+        "z_e0,z_e1\n1.5,-2\n"
+    );
+    let custom = ComponentNames::Custom(vec!["real".into(), "imag".into()]);
+    let text = write(&[""], format.clone().component_names(custom.clone()), &rows);
+    assert_eq!(text, "real,imag\n1.5,-2\n");
+    assert_eq!(
+        read::<Complexf32>(&text, &[""], format.component_names(custom)).unwrap(),
+        rows
+    );
+
+    let o = Octonionf64::e(7) - 1.;
+    assert_eq!(
+        write(
+            &["o"],
+            Format::components().header(false).delimiter(';'),
+            &[vec![o]]
+        ),
+        "-1;0;0;0;0;0;0;1\n"
+    );
+}
+
+#[test]
+fn test_table_read_quoted_and_blank_lines() {
+    let text = "w;x;y;z\r\n\r\n\"1\";2;\" 3 \";4\r\n\n0;0;0;-1\n";
+    let format = Format::components().delimiter(';');
+    let rows = read::<Quaternionf64>(text, &[""], format).unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            vec![complex![1., 2., 3., 4.]],
+            vec![complex![0., 0., 0., -1.]]
+        ]
+    );
+
+    let text = "q\n\"1 + 2i\n + 3j\"\n\"1 + \"\"k\"\"\"\n";
+    let err = read::<Quaternionf64>(text, &["q"], Format::text()).unwrap_err();
+    assert_eq!((err.row, err.column), (4, 1));
+    match err.kind {
+        CsvErrorKind::Parse(err) => assert_eq!(err.kind, ParseErrorKind::UnexpectedCharacter),
+        kind => panic!("unexpected error {:?}", kind),
+    }
+}
+
+#[test]
+fn test_table_streaming() {
+    let mut text = String::from("re,im\n");
+    for n in 0..1000 {
+        text.push_str(&format!("{},{}\n", n, -n));
+    }
+    text.push_str("1,oops\n");
+    let mut reader =
+        Reader::<_, Complexf64>::new(text.as_bytes(), &[""], Format::components()).unwrap();
+
+    for n in 0..1000 {
+        let row = reader.next().unwrap().unwrap();
+        assert_eq!(row, vec![complex![n as f64, -n as f64]]);
+    }
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid table at row 1002, column 2: invalid number `oops`."
+    );
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_table_errors() {
+    let error = |text: &str, layout: Layout| {
+        let format = Format::default().layout(layout);
+        let err = read::<Quaternionf64>(text, &["a", "b"], format).unwrap_err();
+        (err.row, err.column, err.to_string())
+    };
+
+    assert_eq!(
+        error("a_w,a_x,a_y,a_z,b_w,b_x,b_y\n", Layout::Components),
+        (
+            1,
+            8,
+            "Invalid table at row 1, column 8: expected 8 cells but found 7.".into()
+        )
+    );
+    assert_eq!(
+        error("a,c\n", Layout::Text),
+        (
+            1,
+            2,
+            "Invalid table at row 1, column 2: expected header `b` but found `c`.".into()
+        )
+    );
+    assert_eq!(
+        error("", Layout::Text),
+        (
+            1,
+            1,
+            "Invalid table at row 1, column 1: expected 2 cells but found 0.".into()
+        )
+    );
+    assert_eq!(
+        error("a,b\n1i,2j\n1i,2j,3k\n", Layout::Text),
+        (
+            3,
+            3,
+            "Invalid table at row 3, column 3: expected 2 cells but found 3.".into()
+        )
+    );
+    assert_eq!(
+        error("a,b\n1i,2*e4\n", Layout::Text),
+        (
+            2,
+            2,
+            "Invalid table at row 2, column 2: Invalid format for a complex or \
+             hypercomplex type: wrong dimension `2*e4` at byte 0."
+                .into()
+        )
+    );
+    assert_eq!(
+        error("a,b\n1i,\"2j\n", Layout::Text),
+        (
+            2,
+            2,
+            "Invalid table at row 2, column 2: unterminated quote.".into()
+        )
+    );
+}